@@ -1,5 +1,9 @@
 use clap::{Arg, Command};
-use electro_solve_core::{graph::CircuitGraph, units::AngularFrequency};
+use electro_solve::reduce::reduce;
+use electro_solve::units::AngularFrequency;
+use electro_solve::netlist;
+use electro_solve_dsl::ast::{AcRef, Analysis, ElemKind, Element, ElementParams, Program, Quantity, Unit, ValueExpr};
+use electro_solve_dsl::compile::compile;
 use std::fs;
 
 fn main() {
@@ -12,6 +16,15 @@ fn main() {
                 .arg(Arg::new("file").required(true).help("Input circuit file"))
                 .arg(Arg::new("frequency").required(true).help("Frequency in Hz")),
         )
+        .subcommand(
+            Command::new("solve-dsl-demo")
+                .about(
+                    "Compile a hand-built DSL Program and solve it. crates/dsl has no lexer yet to \
+                     turn source text into a Program, so there's no file argument here -- this proves \
+                     out the compile -> reduce path end to end until that lands, at which point this \
+                     demo program should be replaced by one parsed from a real .esdsl file.",
+                ),
+        )
         .get_matches();
 
     match matches.subcommand() {
@@ -20,18 +33,57 @@ fn main() {
             let freq_hz = sub_matches.get_one::<String>("frequency").unwrap();
 
             let freq_hz: f64 = freq_hz.parse().expect("Frequency must be a number");
-            let omega = AngularFrequency::new(freq_hz * 2.0 * std::f64::consts::PI).unwrap();
+            let omega = AngularFrequency::hz(freq_hz);
 
-            // Read and parse circuit file
+            // crates/dsl only has its AST and a `compile(&Program) ->
+            // CircuitGraph` lowering pass so far -- there's no lexer/parser
+            // yet to turn a source file into a `Program`. Until that lands,
+            // `solve` reads the netlist format the rest of ElectroSolve
+            // already understands.
             let content = fs::read_to_string(file_path).expect("Failed to read file");
+            let mut graph = netlist::parse(&content).expect("Failed to parse circuit");
 
-            // Create graph and solve
-            let mut graph = CircuitGraph::new();
-            // TODO: Convert parsed circuit to graph
+            let steps = reduce(&mut graph, omega).expect("Failed to reduce circuit");
 
             println!("Circuit parsed successfully!");
-            println!("Frequency: {} Hz", freq_hz);
-            println!("Omega: {} rad/s", omega.get());
+            println!("Frequency: {freq_hz} Hz");
+            println!("Omega: {} rad/s", f64::from(omega));
+            println!("Reduction steps applied: {}", steps.len());
+
+            match graph.components.iter().find(|c| c.is_active) {
+                Some(component) if graph.active_component_count() == 1 => {
+                    println!("Equivalent impedance: {:?}", component.kind.impedance(omega));
+                }
+                _ => println!(
+                    "Circuit did not reduce to a single equivalent component ({} active remaining); \
+                     solve for a specific pair of terminals with electro_solve::mna::equivalent_impedance instead.",
+                    graph.active_component_count()
+                ),
+            }
+        }
+        Some(("solve-dsl-demo", _)) => {
+            let program = demo_program();
+            let omega = match program.analysis {
+                Some(Analysis::AC { frequency_hz, .. }) => AngularFrequency::hz(frequency_hz),
+                _ => unreachable!("demo_program always sets an AC analysis"),
+            };
+
+            let mut graph = compile(&program).expect("Failed to compile DSL program");
+            let steps = reduce(&mut graph, omega).expect("Failed to reduce circuit");
+
+            println!("DSL program compiled and reduced successfully!");
+            println!("Reduction steps applied: {}", steps.len());
+
+            match graph.components.iter().find(|c| c.is_active) {
+                Some(component) if graph.active_component_count() == 1 => {
+                    println!("Equivalent impedance: {:?}", component.kind.impedance(omega));
+                }
+                _ => println!(
+                    "Circuit did not reduce to a single equivalent component ({} active remaining); \
+                     solve for a specific pair of terminals with electro_solve::mna::equivalent_impedance instead.",
+                    graph.active_component_count()
+                ),
+            }
         }
         _ => {
             eprintln!("No subcommand provided. Use 'solve' to solve a circuit.");
@@ -39,3 +91,34 @@ fn main() {
         }
     }
 }
+
+/// A minimal voltage divider, built directly through the `dsl::ast` types
+/// rather than parsed from source text -- stands in for a real `.esdsl`
+/// file until `crates/dsl` grows a lexer/parser, so `solve-dsl-demo` has
+/// something concrete to feed `compile`.
+fn demo_program() -> Program {
+    let mut program = Program::new();
+    program.ground = Some("0".to_string());
+    program.analysis = Some(Analysis::AC { frequency_hz: 1000.0, ac_ref: AcRef::Rms });
+    program.elements = vec![
+        Element {
+            kind: ElemKind::VoltageSource,
+            id: "V1".to_string(),
+            nodes: ("in".to_string(), "0".to_string()),
+            params: ElementParams::Vdc { value: ValueExpr::Known(Quantity { value_si: 5.0, unit: Unit::Volt }) },
+        },
+        Element {
+            kind: ElemKind::Resistor,
+            id: "R1".to_string(),
+            nodes: ("in".to_string(), "out".to_string()),
+            params: ElementParams::Passive { value: ValueExpr::Known(Quantity { value_si: 100.0, unit: Unit::Ohm }) },
+        },
+        Element {
+            kind: ElemKind::Resistor,
+            id: "R2".to_string(),
+            nodes: ("out".to_string(), "0".to_string()),
+            params: ElementParams::Passive { value: ValueExpr::Known(Quantity { value_si: 200.0, unit: Unit::Ohm }) },
+        },
+    ];
+    program
+}
@@ -0,0 +1,86 @@
+//! Lowers a parsed [`Program`] into a [`CircuitGraph`], the DSL's
+//! counterpart to [`electro_solve::parser::parse_netlist`] for SPICE text.
+
+use std::collections::HashMap;
+
+use electro_solve::component::ComponentKind;
+use electro_solve::errors::CircuitError;
+use electro_solve::graph::{CircuitGraph, NodeIndex};
+use electro_solve::units::{Capacitance, Current, Inductance, Resistance, Voltage};
+
+use crate::ast::{ElemKind, Element, ElementParams, Program, ValueExpr};
+
+/// Walks `program.elements`, interning each distinct node name into a
+/// `NodeIndex` on first sight, maps each element to the matching
+/// `ComponentKind`, and carries `program.ground` over to the graph.
+///
+/// Analyses here are purely numeric (AC/DC), so an element whose value is
+/// `ValueExpr::Unknown` is rejected with a `CircuitError` rather than
+/// silently treated as some default -- symbolic circuits go through
+/// `electro_solve::symbolic::solve_for` instead.
+pub fn compile(program: &Program) -> Result<CircuitGraph, CircuitError> {
+    let mut graph = CircuitGraph::new();
+    let mut node_ids: HashMap<String, NodeIndex> = HashMap::new();
+
+    for element in &program.elements {
+        let n0 = intern_node(&mut graph, &mut node_ids, &element.nodes.0);
+        let n1 = intern_node(&mut graph, &mut node_ids, &element.nodes.1);
+        let kind = compile_element(element)?;
+        graph.add_component(element.id.clone(), kind, (n0, n1));
+    }
+
+    if let Some(ground_name) = &program.ground {
+        let ground_idx = *node_ids.get(ground_name).ok_or_else(|| {
+            CircuitError::CompileError(format!("ground node '{ground_name}' is not wired to any element"))
+        })?;
+        graph.set_ground(ground_idx);
+    }
+
+    Ok(graph)
+}
+
+fn intern_node(graph: &mut CircuitGraph, node_ids: &mut HashMap<String, NodeIndex>, name: &str) -> NodeIndex {
+    if let Some(&idx) = node_ids.get(name) {
+        return idx;
+    }
+    let idx = graph.add_node(name.to_string());
+    node_ids.insert(name.to_string(), idx);
+    idx
+}
+
+fn known_value(value: &ValueExpr, element_id: &str) -> Result<f64, CircuitError> {
+    match value {
+        ValueExpr::Known(quantity) => Ok(quantity.value_si),
+        ValueExpr::Unknown(symbol) => Err(CircuitError::CompileError(format!(
+            "element '{element_id}' has unknown value '{}'; this analysis can't handle unknowns",
+            symbol.0
+        ))),
+    }
+}
+
+fn compile_element(element: &Element) -> Result<ComponentKind, CircuitError> {
+    match (&element.kind, &element.params) {
+        (ElemKind::Resistor, ElementParams::Passive { value }) => {
+            Ok(ComponentKind::Resistor { r: Resistance::known(known_value(value, &element.id)?)? })
+        }
+        (ElemKind::Inductor, ElementParams::Passive { value }) => {
+            Ok(ComponentKind::Inductor { l: Inductance::known(known_value(value, &element.id)?)? })
+        }
+        (ElemKind::Capacitor, ElementParams::Passive { value }) => {
+            Ok(ComponentKind::Capacitor { c: Capacitance::known(known_value(value, &element.id)?)? })
+        }
+        (ElemKind::VoltageSource, ElementParams::Vdc { value }) => {
+            Ok(ComponentKind::VoltageSource { v: Voltage::dc(known_value(value, &element.id)?) })
+        }
+        (ElemKind::VoltageSource, ElementParams::Vac { mag, phase_deg }) => {
+            Ok(ComponentKind::VoltageSource { v: Voltage::ac_phasor(known_value(mag, &element.id)?, *phase_deg) })
+        }
+        (ElemKind::CurrentSource, ElementParams::Idc { value }) => {
+            Ok(ComponentKind::CurrentSource { i: Current::dc(known_value(value, &element.id)?) })
+        }
+        (kind, params) => Err(CircuitError::CompileError(format!(
+            "element '{}' has kind {kind:?} with incompatible parameters {params:?}",
+            element.id
+        ))),
+    }
+}
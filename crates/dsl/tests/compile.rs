@@ -0,0 +1,80 @@
+use electro_solve::component::ComponentKind;
+use electro_solve::units::Value;
+use electro_solve_dsl::ast::{
+    AcRef, Analysis, ElemKind, Element, ElementParams, Program, Quantity, Unit, ValueExpr,
+};
+use electro_solve_dsl::compile::compile;
+
+fn quantity(value_si: f64, unit: Unit) -> ValueExpr {
+    ValueExpr::Known(Quantity { value_si, unit })
+}
+
+#[test]
+fn compiles_a_simple_series_circuit_and_interns_shared_nodes() {
+    let mut program = Program::new();
+    program.ground = Some("0".to_string());
+    program.analysis = Some(Analysis::AC { frequency_hz: 1000.0, ac_ref: AcRef::Rms });
+    program.elements = vec![
+        Element {
+            kind: ElemKind::VoltageSource,
+            id: "V1".to_string(),
+            nodes: ("in".to_string(), "0".to_string()),
+            params: ElementParams::Vdc { value: quantity(5.0, Unit::Volt) },
+        },
+        Element {
+            kind: ElemKind::Resistor,
+            id: "R1".to_string(),
+            nodes: ("in".to_string(), "out".to_string()),
+            params: ElementParams::Passive { value: quantity(100.0, Unit::Ohm) },
+        },
+        Element {
+            kind: ElemKind::Resistor,
+            id: "R2".to_string(),
+            nodes: ("out".to_string(), "0".to_string()),
+            params: ElementParams::Passive { value: quantity(200.0, Unit::Ohm) },
+        },
+    ];
+
+    let graph = compile(&program).unwrap();
+
+    // "in", "out" and "0" are each mentioned twice but should only be
+    // interned once.
+    assert_eq!(graph.nodes.len(), 3);
+    assert_eq!(graph.components.len(), 3);
+    assert!(graph.ground.is_some());
+
+    match &graph.components[1].kind {
+        ComponentKind::Resistor { r } => assert_eq!(r.0, Value::Known(100.0)),
+        other => panic!("expected a resistor, got {other:?}"),
+    }
+}
+
+#[test]
+fn rejects_an_unknown_valued_element() {
+    let mut program = Program::new();
+    program.ground = Some("0".to_string());
+    program.elements = vec![Element {
+        kind: ElemKind::Resistor,
+        id: "R1".to_string(),
+        nodes: ("a".to_string(), "0".to_string()),
+        params: ElementParams::Passive {
+            value: ValueExpr::Unknown(electro_solve_dsl::ast::Symbol("Rload".to_string())),
+        },
+    }];
+
+    assert!(compile(&program).is_err());
+}
+
+#[test]
+fn rejects_a_ground_node_no_element_is_wired_to() {
+    let mut program = Program::new();
+    program.ground = Some("gnd".to_string());
+    program.elements = vec![Element {
+        kind: ElemKind::Resistor,
+        id: "R1".to_string(),
+        nodes: ("a".to_string(), "b".to_string()),
+        params: ElementParams::Passive { value: quantity(100.0, Unit::Ohm) },
+    }];
+
+    assert!(compile(&program).is_err());
+}
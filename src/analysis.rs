@@ -0,0 +1,117 @@
+//! Frequency-domain sweeps over a [`CircuitGraph`], for Bode plots and
+//! impulse-response estimation.
+
+use crate::errors::CircuitError;
+use crate::fft;
+use crate::graph::{CircuitGraph, NodeIndex};
+use crate::mna::equivalent_impedance;
+use crate::units::{AngularFrequency, ImpedanceResult};
+use num_complex::Complex64;
+
+/// Frequency spacing for [`ac_sweep`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scale {
+    Linear,
+    Log,
+}
+
+/// Sweeps the equivalent impedance between `terminal_a` and `terminal_b`
+/// over `points` frequencies from `f_start` to `f_stop` Hz (inclusive),
+/// spaced linearly or logarithmically per `scale`.
+pub fn ac_sweep(
+    graph: &CircuitGraph,
+    terminal_a: NodeIndex,
+    terminal_b: NodeIndex,
+    f_start: f64,
+    f_stop: f64,
+    points: usize,
+    scale: Scale,
+) -> Result<Vec<(f64, ImpedanceResult)>, CircuitError> {
+    if f_start <= 0.0 || f_stop <= 0.0 || f_stop < f_start {
+        return Err(CircuitError::InvalidAngularFrequency(f_stop));
+    }
+    if points == 0 {
+        return Ok(Vec::new());
+    }
+
+    Ok(sweep_frequencies(f_start, f_stop, points, scale)
+        .into_iter()
+        .map(|f| {
+            let omega = AngularFrequency::hz(f);
+            (f, equivalent_impedance(graph, omega, terminal_a, terminal_b))
+        })
+        .collect())
+}
+
+fn sweep_frequencies(f_start: f64, f_stop: f64, points: usize, scale: Scale) -> Vec<f64> {
+    if points == 1 {
+        return vec![f_start];
+    }
+    match scale {
+        Scale::Linear => {
+            let step = (f_stop - f_start) / (points - 1) as f64;
+            (0..points).map(|i| f_start + step * i as f64).collect()
+        }
+        Scale::Log => {
+            let log_start = f_start.ln();
+            let log_stop = f_stop.ln();
+            let step = (log_stop - log_start) / (points - 1) as f64;
+            (0..points).map(|i| (log_start + step * i as f64).exp()).collect()
+        }
+    }
+}
+
+/// Converts a complex impedance to magnitude in decibels, for Bode plots.
+pub fn magnitude_db(z: Complex64) -> f64 {
+    20.0 * z.norm().log10()
+}
+
+/// Converts a complex impedance to phase in degrees, for Bode plots.
+pub fn phase_degrees(z: Complex64) -> f64 {
+    z.arg().to_degrees()
+}
+
+/// Samples the impedance frequency response between `terminal_a` and
+/// `terminal_b` on a uniform grid of `n` points up to `f_max`, enforces
+/// Hermitian symmetry (`H(-omega) = conj(H(omega))`) so the spectrum is
+/// that of a real signal, and runs an inverse FFT to recover the
+/// time-domain response. `n` must be a power of two. Returns the samples
+/// together with their spacing `dt = 1 / (2 * f_max)`.
+pub fn impulse_response(
+    graph: &CircuitGraph,
+    terminal_a: NodeIndex,
+    terminal_b: NodeIndex,
+    f_max: f64,
+    n: usize,
+) -> Result<(Vec<f64>, f64), CircuitError> {
+    if n == 0 || !n.is_power_of_two() {
+        return Err(CircuitError::InvalidFftLength(n));
+    }
+    if f_max <= 0.0 {
+        return Err(CircuitError::InvalidAngularFrequency(f_max));
+    }
+
+    let dt = 1.0 / (2.0 * f_max);
+    let mut spectrum = vec![Complex64::new(0.0, 0.0); n];
+
+    // Bin 0 is DC; bins 1..=n/2 cover 0 < f <= f_max. The remaining bins
+    // are filled in by Hermitian symmetry so the inverse FFT is real.
+    for k in 0..=(n / 2) {
+        let f = k as f64 * f_max / (n / 2) as f64;
+        let omega = AngularFrequency::hz(f);
+        let h = match equivalent_impedance(graph, omega, terminal_a, terminal_b) {
+            ImpedanceResult::Finite(z) => z,
+            ImpedanceResult::Open | ImpedanceResult::Short => Complex64::new(0.0, 0.0),
+        };
+        spectrum[k] = h;
+        if k != 0 && k != n / 2 {
+            spectrum[n - k] = h.conj();
+        }
+    }
+
+    if !fft::inverse(&mut spectrum) {
+        return Err(CircuitError::InvalidFftLength(n));
+    }
+
+    Ok((spectrum.iter().map(|c| c.re).collect(), dt))
+}
@@ -1,8 +1,10 @@
 use num_complex::Complex64;
 use crate::units::*;
 use crate::errors::CircuitError;
+use crate::graph::{ComponentIndex, NodeIndex};
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ComponentKind {
     Resistor {r: Resistance},
     Inductor  {l: Inductance},
@@ -10,6 +12,23 @@ pub enum ComponentKind {
     Impedance {z: ImpedanceResult},
     VoltageSource {v: Voltage},
     CurrentSource {i: Current},
+    /// Voltage-controlled voltage source (SPICE `E`):
+    /// `v(nodes.0) - v(nodes.1) = gain * (v(control_nodes.0) - v(control_nodes.1))`.
+    VCVS { gain: f64, control_nodes: (NodeIndex, NodeIndex) },
+    /// Voltage-controlled current source (SPICE `G`): drives
+    /// `gain * (v(control_nodes.0) - v(control_nodes.1))` through itself
+    /// from `nodes.0` to `nodes.1`.
+    VCCS { gain: f64, control_nodes: (NodeIndex, NodeIndex) },
+    /// Current-controlled voltage source (SPICE `H`):
+    /// `v(nodes.0) - v(nodes.1) = gain * i_control`, where `i_control` is
+    /// the branch current through `control_component` -- which must be a
+    /// component with its own MNA branch-current unknown, i.e. a
+    /// `VoltageSource`, `VCVS` or `CCVS`.
+    CCVS { gain: f64, control_component: ComponentIndex },
+    /// Current-controlled current source (SPICE `F`): drives
+    /// `gain * i_control` through itself from `nodes.0` to `nodes.1`, sensed the same way
+    /// as [`ComponentKind::CCVS`].
+    CCCS { gain: f64, control_component: ComponentIndex },
 }
 
 impl ComponentKind {
@@ -41,7 +60,8 @@ impl ComponentKind {
                 }
             },
             Self::Impedance {z} => z.clone(),
-            Self::VoltageSource {..} | Self::CurrentSource {..} => ImpedanceResult::Short
+            Self::VoltageSource {..} | Self::CurrentSource {..}
+            | Self::VCVS {..} | Self::VCCS {..} | Self::CCVS {..} | Self::CCCS {..} => ImpedanceResult::Short,
         }
     }
 
@@ -54,9 +74,38 @@ impl ComponentKind {
     }
 
     pub fn is_source(&self) -> bool {
-        matches!(self, Self::VoltageSource {..} | Self::CurrentSource {..})
+        matches!(
+            self,
+            Self::VoltageSource {..} | Self::CurrentSource {..}
+                | Self::VCVS {..} | Self::VCCS {..} | Self::CCVS {..} | Self::CCCS {..}
+        )
     }
 
+    /// Combines `self` and `other` into the single component equivalent
+    /// to wiring them in series, if they're the same kind of known
+    /// passive component. `None` for any other pairing (mismatched
+    /// kinds, a source, or a symbolic/unknown value), in which case
+    /// the pair isn't a series-reducible candidate at all.
+    pub fn combine_series(&self, other: &ComponentKind) -> Option<ComponentKind> {
+        match (self, other) {
+            (Self::Resistor {r: r1}, Self::Resistor {r: r2}) => r1.in_series(r2).map(|r| Self::Resistor {r}),
+            (Self::Inductor {l: l1}, Self::Inductor {l: l2}) => l1.in_series(l2).map(|l| Self::Inductor {l}),
+            (Self::Capacitor {c: c1}, Self::Capacitor {c: c2}) => c1.in_series(c2).map(|c| Self::Capacitor {c}),
+            _ => None,
+        }
+    }
+
+    /// Combines `self` and `other` into the single component equivalent
+    /// to wiring them in parallel, under the same restrictions as
+    /// [`ComponentKind::combine_series`].
+    pub fn combine_parallel(&self, other: &ComponentKind) -> Option<ComponentKind> {
+        match (self, other) {
+            (Self::Resistor {r: r1}, Self::Resistor {r: r2}) => r1.in_parallel(r2).map(|r| Self::Resistor {r}),
+            (Self::Inductor {l: l1}, Self::Inductor {l: l2}) => l1.in_parallel(l2).map(|l| Self::Inductor {l}),
+            (Self::Capacitor {c: c1}, Self::Capacitor {c: c2}) => c1.in_parallel(c2).map(|c| Self::Capacitor {c}),
+            _ => None,
+        }
+    }
 }
 
 pub fn impedance_to_kind(z: ImpedanceResult) -> Result<ComponentKind, CircuitError> {
@@ -15,15 +15,23 @@ pub enum CircuitError {
     InvalidCapacitance(f64),
     #[error("Invalid impedance: {0} Ω (must be > 0 and finite)")]
     InvalidImpedance(Complex64),
+    #[error("Invalid FFT length: {0} (must be a nonzero power of two)")]
+    InvalidFftLength(usize),
+    #[error("Cannot solve symbolically: {0}")]
+    SymbolicSolveFailed(String),
+    #[error("DSL compile error: {0}")]
+    CompileError(String),
+    #[error("Voltage sources form a loop between nodes {0} and {1}, over-determining the node voltages")]
+    OverdeterminedSupernode(usize, usize),
 }
 
 #[derive(Debug, Error)]
-#[error("Parse error on line {line}: {message}")]
-pub struct ParseError { pub line: usize, pub message: String }
+#[error("Parse error on line {line}, column {column}: {message}")]
+pub struct ParseError { pub line: usize, pub column: usize, pub message: String }
 
 impl From<CircuitError> for ParseError {
     fn from(value: CircuitError) -> Self {
         // FIXME: This is temporary. We need proprer line number reporting.
-        Self { line: 0, message: format!("{}", value) }
+        Self { line: 0, column: 0, message: format!("{}", value) }
     }
 }
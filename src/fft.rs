@@ -0,0 +1,74 @@
+//! Radix-2 Cooley-Tukey FFT over [`Complex64`], shared by the frequency
+//! sweep and transient-response analyses to move between the time and
+//! frequency domains.
+
+use num_complex::Complex64;
+use std::f64::consts::PI;
+
+/// Bit-reversal permutation, the first stage of an in-place radix-2 FFT.
+fn bit_reverse_permute(data: &mut [Complex64]) {
+    let n = data.len();
+    if n <= 1 {
+        return;
+    }
+    let bits = n.trailing_zeros();
+    for i in 0..n {
+        let j = (i as u32).reverse_bits() >> (32 - bits);
+        let j = j as usize;
+        if j > i {
+            data.swap(i, j);
+        }
+    }
+}
+
+fn fft_in_place(data: &mut [Complex64], inverse: bool) -> bool {
+    let n = data.len();
+    if n == 0 || !n.is_power_of_two() {
+        return false;
+    }
+
+    bit_reverse_permute(data);
+
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let mut size = 2;
+    while size <= n {
+        let half = size / 2;
+        let w_step = Complex64::from_polar(1.0, sign * 2.0 * PI / size as f64);
+        let mut start = 0;
+        while start < n {
+            let mut w = Complex64::new(1.0, 0.0);
+            for k in 0..half {
+                let even = data[start + k];
+                let odd = data[start + k + half] * w;
+                data[start + k] = even + odd;
+                data[start + k + half] = even - odd;
+                w *= w_step;
+            }
+            start += size;
+        }
+        size *= 2;
+    }
+
+    if inverse {
+        let scale = 1.0 / n as f64;
+        for value in data.iter_mut() {
+            *value *= scale;
+        }
+    }
+    true
+}
+
+/// Computes the forward DFT of `data` in place using the radix-2 FFT
+/// (bit-reversal permutation followed by `log2(n)` butterfly stages with
+/// twiddle factors `exp(-2*pi*i*k/n)`). `data.len()` must be a nonzero
+/// power of two; returns `false` otherwise.
+pub fn forward(data: &mut [Complex64]) -> bool {
+    fft_in_place(data, false)
+}
+
+/// Computes the inverse DFT of `data` in place using the radix-2 FFT
+/// (conjugated twiddle factors, scaled by `1/n`). `data.len()` must be a
+/// nonzero power of two; returns `false` otherwise.
+pub fn inverse(data: &mut [Complex64]) -> bool {
+    fft_in_place(data, true)
+}
@@ -0,0 +1,193 @@
+//! A small hand-rolled grammar engine for SPICE netlist lines.
+//!
+//! There's no `pest` dependency anywhere in this tree, so rather than add
+//! one this follows the shape pest itself generates -- a `Rule` enum
+//! naming each grammar production, and a tree of matched `Pair`s with
+//! byte-column spans -- by hand. A later migration to an actual `.pest`
+//! grammar file should only need to replace [`parse_line`]'s body; the
+//! `Rule`/`Pair` shape callers walk stays the same.
+//!
+//! The grammar itself, informally:
+//!
+//! ```text
+//! line      = comment | directive | instance | component
+//! comment   = "*" rest_of_line
+//! directive = "." keyword value*
+//! instance  = "X" component_id node_ref+ value
+//! component = component_id node_ref node_ref value+
+//! ```
+//!
+//! Trailing `;` comments and `+`-prefixed continuation lines are handled
+//! a layer up in [`logical_lines`], before a single logical line ever
+//! reaches [`parse_line`].
+
+/// One grammar production, analogous to a pest-generated `Rule` variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    Comment,
+    Directive,
+    Instance,
+    Component,
+    ComponentId,
+    Keyword,
+    NodeRef,
+    Value,
+}
+
+/// A matched grammar rule: which [`Rule`] it is, the source slice it
+/// covered, the 1-based column it started at, and any child matches --
+/// the hand-rolled equivalent of pest's `Pair<Rule>`.
+#[derive(Debug, Clone)]
+pub struct Pair<'a> {
+    pub rule: Rule,
+    pub text: &'a str,
+    pub column: usize,
+    pub children: Vec<Pair<'a>>,
+}
+
+impl<'a> Pair<'a> {
+    fn leaf(rule: Rule, column: usize, text: &'a str) -> Self {
+        Self { rule, text, column, children: Vec::new() }
+    }
+
+    /// Children matching a given rule, in source order -- the hand-rolled
+    /// equivalent of filtering a pest `Pairs` iterator by `as_rule()`.
+    pub fn children_of(&self, rule: Rule) -> impl Iterator<Item = &Pair<'a>> {
+        self.children.iter().filter(move |c| c.rule == rule)
+    }
+}
+
+/// A grammar violation, with the 1-based column it was found at so
+/// callers can build a precise [`crate::errors::ParseError`] instead of
+/// only knowing the line.
+#[derive(Debug, Clone)]
+pub struct GrammarError {
+    pub column: usize,
+    pub message: String,
+}
+
+pub(crate) fn tokens_with_columns(text: &str) -> Vec<(usize, &str)> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some(&(idx, ch)) = chars.peek() {
+        if ch.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        let start = idx;
+        let mut end = idx + ch.len_utf8();
+        chars.next();
+        while let Some(&(next_idx, next_ch)) = chars.peek() {
+            if next_ch.is_whitespace() {
+                break;
+            }
+            end = next_idx + next_ch.len_utf8();
+            chars.next();
+        }
+        tokens.push((start + 1, &text[start..end]));
+    }
+    tokens
+}
+
+/// Parses a single already-joined, already-comment-stripped logical line
+/// into its `Pair` tree. An all-whitespace line parses as an empty
+/// [`Rule::Comment`] rather than an error, same as a `*`-prefixed one --
+/// both are no-ops to the caller.
+pub fn parse_line(text: &str) -> Result<Pair<'_>, GrammarError> {
+    let tokens = tokens_with_columns(text);
+    let Some(&(first_column, first_token)) = tokens.first() else {
+        let blank_column = text.len() - text.trim_start().len() + 1;
+        return Ok(Pair::leaf(Rule::Comment, blank_column, text));
+    };
+
+    if first_token.starts_with('*') {
+        return Ok(Pair::leaf(Rule::Comment, first_column, text));
+    }
+
+    if let Some(keyword) = first_token.strip_prefix('.') {
+        if keyword.is_empty() {
+            return Err(GrammarError {
+                column: first_column,
+                message: "a directive needs a name after '.'".to_string(),
+            });
+        }
+        let mut children = vec![Pair::leaf(Rule::Keyword, first_column + 1, keyword)];
+        children.extend(
+            tokens[1..].iter().map(|&(column, token)| Pair::leaf(Rule::Value, column, token)),
+        );
+        return Ok(Pair { rule: Rule::Directive, text, column: first_column, children });
+    }
+
+    if first_token.starts_with('X') {
+        // Xname n1 n2 ... SUBNAME -- unlike a component, a subcircuit
+        // instance's port count is only known once its `.subckt`
+        // definition is looked up, so every token but the id and the
+        // trailing subcircuit name is a node reference.
+        if tokens.len() < 3 {
+            return Err(GrammarError {
+                column: first_column,
+                message: format!(
+                    "expected an instance id, at least one node reference and a subcircuit name, got {} token(s)",
+                    tokens.len()
+                ),
+            });
+        }
+        let mut children = vec![Pair::leaf(Rule::ComponentId, tokens[0].0, tokens[0].1)];
+        children.extend(
+            tokens[1..tokens.len() - 1].iter().map(|&(column, token)| Pair::leaf(Rule::NodeRef, column, token)),
+        );
+        let (name_column, name_text) = tokens[tokens.len() - 1];
+        children.push(Pair::leaf(Rule::Value, name_column, name_text));
+        return Ok(Pair { rule: Rule::Instance, text, column: first_column, children });
+    }
+
+    if tokens.len() < 4 {
+        return Err(GrammarError {
+            column: first_column,
+            message: format!(
+                "expected a component id, two node references and a value, got {} token(s)",
+                tokens.len()
+            ),
+        });
+    }
+
+    let mut children = vec![
+        Pair::leaf(Rule::ComponentId, tokens[0].0, tokens[0].1),
+        Pair::leaf(Rule::NodeRef, tokens[1].0, tokens[1].1),
+        Pair::leaf(Rule::NodeRef, tokens[2].0, tokens[2].1),
+    ];
+    children.extend(tokens[3..].iter().map(|&(column, token)| Pair::leaf(Rule::Value, column, token)));
+
+    Ok(Pair { rule: Rule::Component, text, column: first_column, children })
+}
+
+fn strip_trailing_comment(line: &str) -> &str {
+    match line.find(';') {
+        Some(idx) => &line[..idx],
+        None => line,
+    }
+}
+
+/// Splits `input` into logical lines: trailing `;` comments are cut off,
+/// and any line whose first non-whitespace character is `+` is folded
+/// into the previous logical line as a continuation (the usual SPICE
+/// convention for a long component or directive line). Each entry is the
+/// 1-based physical line number the logical line *started* on (used for
+/// error reporting) paired with the joined, comment-stripped text.
+pub fn logical_lines(input: &str) -> Vec<(usize, String)> {
+    let mut out: Vec<(usize, String)> = Vec::new();
+    for (idx, raw) in input.lines().enumerate() {
+        let line_num = idx + 1;
+        let stripped = strip_trailing_comment(raw);
+        let trimmed = stripped.trim_start();
+        if let Some(continued) = trimmed.strip_prefix('+') {
+            if let Some((_, joined)) = out.last_mut() {
+                joined.push(' ');
+                joined.push_str(continued.trim());
+                continue;
+            }
+        }
+        out.push((line_num, stripped.to_string()));
+    }
+    out
+}
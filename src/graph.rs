@@ -1,15 +1,19 @@
 use crate::component::ComponentKind;
-use crate::units::{AngularFrequency, ImpedanceResult};
+use crate::errors::CircuitError;
+use crate::units::{AngularFrequency, ImpedanceResult, Value, Voltage};
+use std::collections::{HashMap, HashSet};
 
 pub type NodeIndex = usize;
 pub type ComponentIndex = usize;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Node {
     pub id: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CircuitComponent {
     pub id: String,
     pub kind: ComponentKind,
@@ -18,7 +22,8 @@ pub struct CircuitComponent {
     pub cached_impedance: Option<ImpedanceResult>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CircuitGraph {
     pub nodes: Vec<Node>,
     pub components: Vec<CircuitComponent>,
@@ -101,4 +106,569 @@ impl CircuitGraph {
     pub fn is_ground(&self, idx: NodeIndex) -> bool {
         self.ground.map_or(false, |g| g == idx)
     }
+
+    /// Groups every node into its connected component, using active
+    /// components as edges -- an infinite-impedance current source still
+    /// counts, since this is about galvanic connectivity, not whether
+    /// current actually flows. A node with no active connections forms
+    /// its own singleton component.
+    pub fn connected_components(&self) -> Vec<Vec<NodeIndex>> {
+        let mut uf = self.node_union_find();
+        let mut groups: HashMap<NodeIndex, Vec<NodeIndex>> = HashMap::new();
+        for idx in 0..self.nodes.len() {
+            groups.entry(uf.find(idx)).or_default().push(idx);
+        }
+        groups.into_values().collect()
+    }
+
+    /// Whether every node has a galvanic path, through active
+    /// components, to the graph's declared ground node. `false` if no
+    /// ground has been set.
+    pub fn is_fully_connected_to_ground(&self) -> bool {
+        let Some(ground) = self.ground else { return false };
+        let mut uf = self.node_union_find();
+        let ground_root = uf.find(ground);
+        (0..self.nodes.len()).all(|idx| uf.find(idx) == ground_root)
+    }
+
+    fn node_union_find(&self) -> UnionFind {
+        let mut uf = UnionFind::new(self.nodes.len());
+        for comp in &self.components {
+            if comp.is_active {
+                uf.union(comp.nodes.0, comp.nodes.1);
+            }
+        }
+        uf
+    }
+
+    /// Simplifies passive sub-networks before solving, as classic
+    /// SPICE-style simulators do -- unlike [`crate::reduce::reduce`],
+    /// this works directly on component values rather than a cached
+    /// impedance at one frequency, so it needs no angular frequency and
+    /// can run before `cache_impedances` is ever called.
+    ///
+    /// Series rule: any non-ground node of degree exactly 2 connecting
+    /// two components of the same known passive kind is eliminated,
+    /// merging them into one equivalent component and leaving the node
+    /// itself with no active connections -- spliced out of the
+    /// adjacency lists in effect, even though its slot in `self.nodes`
+    /// stays put, the same way a deactivated component's old endpoints
+    /// are left in place elsewhere in this module. Parallel rule: two
+    /// active components of the same known passive kind sharing both
+    /// endpoints collapse into one. Both rules alternate until neither
+    /// fires.
+    ///
+    /// Returns a map from every original component index consumed by a
+    /// merge to the index of the component it was ultimately folded
+    /// into, so solved results can be back-annotated onto the original
+    /// netlist.
+    pub fn reduce_series_parallel(&mut self) -> HashMap<ComponentIndex, ComponentIndex> {
+        let mut equivalent_of: HashMap<ComponentIndex, ComponentIndex> = HashMap::new();
+        loop {
+            if let Some((node_idx, idx1, idx2)) = self.find_series_candidate() {
+                self.merge_series(node_idx, idx1, idx2, &mut equivalent_of);
+                continue;
+            }
+            if let Some((idx1, idx2)) = self.find_parallel_candidate() {
+                self.merge_parallel(idx1, idx2, &mut equivalent_of);
+                continue;
+            }
+            break;
+        }
+        equivalent_of
+    }
+
+    fn find_series_candidate(&self) -> Option<(NodeIndex, ComponentIndex, ComponentIndex)> {
+        for node_idx in 0..self.nodes.len() {
+            if self.is_ground(node_idx) {
+                continue;
+            }
+            let connected = self.connections_at(node_idx);
+            if connected.len() != 2 {
+                continue;
+            }
+            let (idx1, idx2) = (connected[0], connected[1]);
+            let (comp1, comp2) = (&self.components[idx1], &self.components[idx2]);
+            if same_node_pair(comp1.nodes, comp2.nodes) {
+                // comp1 and comp2 already connect the very same two nodes --
+                // this node is one end of a parallel pair, not a pass-through
+                // in a chain, and treating it as series would splice in a
+                // self-loop. Let the parallel rule handle it instead.
+                continue;
+            }
+            if comp1.kind.combine_series(&comp2.kind).is_some() {
+                return Some((node_idx, idx1, idx2));
+            }
+        }
+        None
+    }
+
+    fn find_parallel_candidate(&self) -> Option<(ComponentIndex, ComponentIndex)> {
+        for idx1 in 0..self.components.len() {
+            if !self.components[idx1].is_active {
+                continue;
+            }
+            for idx2 in (idx1 + 1)..self.components.len() {
+                let comp1 = &self.components[idx1];
+                let comp2 = &self.components[idx2];
+                if !comp2.is_active || !same_node_pair(comp1.nodes, comp2.nodes) {
+                    continue;
+                }
+                if comp1.kind.combine_parallel(&comp2.kind).is_some() {
+                    return Some((idx1, idx2));
+                }
+            }
+        }
+        None
+    }
+
+    fn merge_series(
+        &mut self,
+        node_idx: NodeIndex,
+        idx1: ComponentIndex,
+        idx2: ComponentIndex,
+        equivalent_of: &mut HashMap<ComponentIndex, ComponentIndex>,
+    ) {
+        let comp1 = &self.components[idx1];
+        let comp2 = &self.components[idx2];
+        let outer1 = if comp1.nodes.0 == node_idx { comp1.nodes.1 } else { comp1.nodes.0 };
+        let outer2 = if comp2.nodes.0 == node_idx { comp2.nodes.1 } else { comp2.nodes.0 };
+        let kind = comp1.kind.combine_series(&comp2.kind).expect("caller already confirmed this pair combines");
+
+        self.components[idx1].is_active = false;
+        self.components[idx2].is_active = false;
+        let new_idx = self.add_component("EQ".to_string(), kind, (outer1, outer2));
+
+        record_merge(equivalent_of, &[idx1, idx2], new_idx);
+    }
+
+    fn merge_parallel(&mut self, idx1: ComponentIndex, idx2: ComponentIndex, equivalent_of: &mut HashMap<ComponentIndex, ComponentIndex>) {
+        let comp1 = &self.components[idx1];
+        let comp2 = &self.components[idx2];
+        let nodes = comp1.nodes;
+        let kind = comp1.kind.combine_parallel(&comp2.kind).expect("caller already confirmed this pair combines");
+
+        self.components[idx1].is_active = false;
+        self.components[idx2].is_active = false;
+        let new_idx = self.add_component("EQ".to_string(), kind, nodes);
+
+        record_merge(equivalent_of, &[idx1, idx2], new_idx);
+    }
+
+    /// Returns the independent loops needed for mesh (loop) analysis, as
+    /// a complement to the nodal-analysis adjacency model the rest of
+    /// this struct is built around.
+    ///
+    /// A spanning forest of the active-component graph is built by DFS;
+    /// every active component left over once the forest is built (a
+    /// "chord") closes exactly one fundamental loop together with the
+    /// unique tree path between its two endpoints, giving `E - N +
+    /// components` loops in total -- the standard count of independent
+    /// KVL equations. Each loop is returned as an ordered list of
+    /// `(component_idx, direction)` pairs, starting at the chord itself
+    /// and walking the tree path back to the chord's other endpoint.
+    pub fn fundamental_loops(&self) -> Vec<Vec<(ComponentIndex, LoopDirection)>> {
+        let n = self.nodes.len();
+        let mut parent_node: Vec<Option<NodeIndex>> = vec![None; n];
+        let mut parent_edge: Vec<Option<ComponentIndex>> = vec![None; n];
+        let mut depth: Vec<usize> = vec![0; n];
+        let mut visited = vec![false; n];
+        let mut tree_edges: HashSet<ComponentIndex> = HashSet::new();
+
+        for start in 0..n {
+            if visited[start] {
+                continue;
+            }
+            visited[start] = true;
+            let mut stack = vec![start];
+            while let Some(node) = stack.pop() {
+                for comp_idx in self.connections_at(node) {
+                    let comp = &self.components[comp_idx];
+                    let other = if comp.nodes.0 == node { comp.nodes.1 } else { comp.nodes.0 };
+                    if !visited[other] {
+                        visited[other] = true;
+                        parent_node[other] = Some(node);
+                        parent_edge[other] = Some(comp_idx);
+                        depth[other] = depth[node] + 1;
+                        tree_edges.insert(comp_idx);
+                        stack.push(other);
+                    }
+                }
+            }
+        }
+
+        let mut loops = Vec::new();
+        for (comp_idx, comp) in self.components.iter().enumerate() {
+            if !comp.is_active || tree_edges.contains(&comp_idx) {
+                continue;
+            }
+            let (a, b) = comp.nodes;
+            let lca = lowest_common_ancestor(a, b, &parent_node, &depth);
+
+            let mut loop_edges = vec![(comp_idx, LoopDirection::Forward)];
+            loop_edges.extend(path_to_ancestor(b, lca, &parent_node, &parent_edge, &self.components));
+
+            let mut down = path_to_ancestor(a, lca, &parent_node, &parent_edge, &self.components);
+            down.reverse();
+            loop_edges.extend(down.into_iter().map(|(idx, dir)| (idx, dir.flipped())));
+
+            loops.push(loop_edges);
+        }
+        loops
+    }
+
+    /// Groups nodes tied together by active voltage sources between two
+    /// non-ground nodes into supernodes, as modified nodal analysis
+    /// requires: each such group collapses to a single KCL equation plus
+    /// one constraint equation per voltage source, `V(nodes.0) -
+    /// V(nodes.1) = value`. A voltage source with one endpoint at ground
+    /// is left out of the grouping -- plain nodal analysis already fixes
+    /// that node's voltage directly, so it needs no supernode treatment.
+    ///
+    /// Returns [`CircuitError::OverdeterminedSupernode`] if the voltage
+    /// sources form a loop among themselves (e.g. two in parallel, or
+    /// three around a triangle), which over-determines the node
+    /// voltages -- detected when a source's two endpoints are already in
+    /// the same supernode before that source is unioned in.
+    pub fn supernodes(&self) -> Result<Vec<Supernode>, CircuitError> {
+        let mut uf = UnionFind::new(self.nodes.len());
+        let mut constraints = Vec::new();
+
+        for comp in &self.components {
+            if !comp.is_active {
+                continue;
+            }
+            let ComponentKind::VoltageSource { v } = &comp.kind else { continue };
+            let (a, b) = comp.nodes;
+            if self.is_ground(a) || self.is_ground(b) {
+                continue;
+            }
+            if uf.find(a) == uf.find(b) {
+                return Err(CircuitError::OverdeterminedSupernode(a, b));
+            }
+            uf.union(a, b);
+            constraints.push(SupernodeConstraint { nodes: (a, b), voltage: *v });
+        }
+
+        let mut groups: HashMap<NodeIndex, Vec<NodeIndex>> = HashMap::new();
+        for idx in 0..self.nodes.len() {
+            groups.entry(uf.find(idx)).or_default().push(idx);
+        }
+        let mut constraints_by_root: HashMap<NodeIndex, Vec<SupernodeConstraint>> = HashMap::new();
+        for constraint in constraints {
+            constraints_by_root.entry(uf.find(constraint.nodes.0)).or_default().push(constraint);
+        }
+
+        Ok(groups
+            .into_iter()
+            .filter(|(_, nodes)| nodes.len() > 1)
+            .map(|(root, nodes)| Supernode {
+                nodes,
+                constraints: constraints_by_root.remove(&root).unwrap_or_default(),
+            })
+            .collect())
+    }
+
+    /// Renders this circuit's topology as Graphviz DOT text, so the
+    /// structure these adjacency/connection queries operate over -- and
+    /// the effect of a reduction pass like
+    /// [`CircuitGraph::reduce_series_parallel`] -- can be checked
+    /// visually. Each node becomes a vertex, with the ground node drawn
+    /// filled; each component becomes a labeled edge between `nodes.0`
+    /// and `nodes.1` carrying its id and value (e.g. `"R1 100Ω"`, `"V2
+    /// 5+0iV"`). Inactive components are kept as dashed edges rather than
+    /// dropped, so a reduced circuit can still be compared against its
+    /// original topology.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("graph Circuit {\n");
+        for (idx, node) in self.nodes.iter().enumerate() {
+            if self.is_ground(idx) {
+                dot.push_str(&format!("    N{idx} [label=\"{}\", shape=doublecircle, style=filled];\n", node.id));
+            } else {
+                dot.push_str(&format!("    N{idx} [label=\"{}\"];\n", node.id));
+            }
+        }
+        for comp in &self.components {
+            let style = if comp.is_active { "solid" } else { "dashed" };
+            dot.push_str(&format!(
+                "    N{} -- N{} [label=\"{} {}\", style={style}];\n",
+                comp.nodes.0, comp.nodes.1, comp.id, component_dot_label(&comp.kind)
+            ));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Decides whether `self` and `other` are structurally and
+    /// electrically equivalent circuits: a VF2-style backtracking search
+    /// for a bijection between their node indices under which every
+    /// active component in one graph has a matching component (same
+    /// [`ComponentKind`], same parameter value) spanning the
+    /// corresponding mapped nodes in the other, and vice versa. Ground
+    /// must map to ground. Useful for asserting that a graph
+    /// transformation -- re-indexing nodes, or a reduction pass like
+    /// [`CircuitGraph::reduce_series_parallel`] -- preserved the circuit.
+    ///
+    /// Returns the node permutation (`self` index -> `other` index) on
+    /// success, `None` if no such mapping exists.
+    pub fn is_isomorphic_to(&self, other: &CircuitGraph) -> Option<Vec<NodeIndex>> {
+        if self.nodes.len() != other.nodes.len() {
+            return None;
+        }
+        if self.active_component_count() != other.active_component_count() {
+            return None;
+        }
+        if self.ground.is_some() != other.ground.is_some() {
+            return None;
+        }
+
+        let mut order: Vec<NodeIndex> = (0..self.nodes.len()).collect();
+        order.sort_by_key(|&idx| std::cmp::Reverse(self.get_node_degree(idx)));
+
+        let mut mapping: Vec<Option<NodeIndex>> = vec![None; self.nodes.len()];
+        let mut used = vec![false; other.nodes.len()];
+        if self.extend_isomorphism(0, &order, other, &mut mapping, &mut used) {
+            Some(mapping.into_iter().map(|m| m.expect("every node was assigned an image")).collect())
+        } else {
+            None
+        }
+    }
+
+    /// Tries every still-unused node in `other` as the image of
+    /// `order[pos]`, recursing once a feasible candidate is found and
+    /// backtracking otherwise -- the core VF2 search step.
+    fn extend_isomorphism(
+        &self,
+        pos: usize,
+        order: &[NodeIndex],
+        other: &CircuitGraph,
+        mapping: &mut Vec<Option<NodeIndex>>,
+        used: &mut Vec<bool>,
+    ) -> bool {
+        if pos == order.len() {
+            return true;
+        }
+        let u = order[pos];
+        for v in 0..other.nodes.len() {
+            if used[v] || self.is_ground(u) != other.is_ground(v) || self.get_node_degree(u) != other.get_node_degree(v) {
+                continue;
+            }
+            if !self.mapped_edges_match(u, v, mapping, other) {
+                continue;
+            }
+            mapping[u] = Some(v);
+            used[v] = true;
+            if self.extend_isomorphism(pos + 1, order, other, mapping, used) {
+                return true;
+            }
+            mapping[u] = None;
+            used[v] = false;
+        }
+        false
+    }
+
+    /// Whether candidate image `v` is consistent with every node already
+    /// mapped: for each already-mapped node `w`, the multiset of active
+    /// component kinds between `u` and `w` in `self` must match the
+    /// multiset between `v` and `mapping[w]` in `other`.
+    fn mapped_edges_match(&self, u: NodeIndex, v: NodeIndex, mapping: &[Option<NodeIndex>], other: &CircuitGraph) -> bool {
+        for (w, &mapped) in mapping.iter().enumerate() {
+            let Some(v_w) = mapped else { continue };
+            let self_kinds = self.components_between(u, w);
+            let other_kinds = other.components_between(v, v_w);
+            if !kind_multisets_equal(&self_kinds, &other_kinds) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// The kinds of every active component directly connecting `a` and
+    /// `b`.
+    fn components_between(&self, a: NodeIndex, b: NodeIndex) -> Vec<ComponentKind> {
+        self.connections_at(a)
+            .into_iter()
+            .filter(|&idx| same_node_pair(self.components[idx].nodes, (a, b)))
+            .map(|idx| self.components[idx].kind.clone())
+            .collect()
+    }
+}
+
+/// Whether `a` and `b` contain the same [`ComponentKind`]s with the same
+/// multiplicities, ignoring order.
+fn kind_multisets_equal(a: &[ComponentKind], b: &[ComponentKind]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut remaining: Vec<&ComponentKind> = b.iter().collect();
+    for kind in a {
+        match remaining.iter().position(|&k| k == kind) {
+            Some(pos) => {
+                remaining.remove(pos);
+            }
+            None => return false,
+        }
+    }
+    true
+}
+
+/// A group of nodes tied together by active voltage sources, returned by
+/// [`CircuitGraph::supernodes`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Supernode {
+    pub nodes: Vec<NodeIndex>,
+    pub constraints: Vec<SupernodeConstraint>,
+}
+
+/// One voltage source's constraint within a [`Supernode`]:
+/// `V(nodes.0) - V(nodes.1) = voltage`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SupernodeConstraint {
+    pub nodes: (NodeIndex, NodeIndex),
+    pub voltage: Voltage,
+}
+
+/// Which way a [`fundamental_loops`](CircuitGraph::fundamental_loops) walk
+/// traverses one of its component edges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LoopDirection {
+    /// Traversed from `nodes.0` to `nodes.1`.
+    Forward,
+    /// Traversed from `nodes.1` to `nodes.0`.
+    Reverse,
+}
+
+impl LoopDirection {
+    fn flipped(self) -> Self {
+        match self {
+            LoopDirection::Forward => LoopDirection::Reverse,
+            LoopDirection::Reverse => LoopDirection::Forward,
+        }
+    }
+}
+
+/// Walks from `node` up the spanning-tree parent pointers to `ancestor`,
+/// collecting each tree edge as a `(component_idx, direction)` pair in
+/// the child-to-parent direction it was actually traversed.
+fn path_to_ancestor(
+    mut node: NodeIndex,
+    ancestor: NodeIndex,
+    parent_node: &[Option<NodeIndex>],
+    parent_edge: &[Option<ComponentIndex>],
+    components: &[CircuitComponent],
+) -> Vec<(ComponentIndex, LoopDirection)> {
+    let mut path = Vec::new();
+    while node != ancestor {
+        let comp_idx = parent_edge[node].expect("every non-root tree node has a parent edge");
+        let direction = if components[comp_idx].nodes.0 == node { LoopDirection::Forward } else { LoopDirection::Reverse };
+        path.push((comp_idx, direction));
+        node = parent_node[node].expect("every non-root tree node has a parent node");
+    }
+    path
+}
+
+/// Finds the lowest common ancestor of `u` and `v` in the spanning forest
+/// described by `parent_node`/`depth`, by walking the shallower node up
+/// to the deeper node's depth and then both up together until they meet.
+fn lowest_common_ancestor(mut u: NodeIndex, mut v: NodeIndex, parent_node: &[Option<NodeIndex>], depth: &[usize]) -> NodeIndex {
+    while depth[u] > depth[v] {
+        u = parent_node[u].expect("a node deeper than another has a parent");
+    }
+    while depth[v] > depth[u] {
+        v = parent_node[v].expect("a node deeper than another has a parent");
+    }
+    while u != v {
+        u = parent_node[u].expect("distinct nodes in the same tree share an ancestor");
+        v = parent_node[v].expect("distinct nodes in the same tree share an ancestor");
+    }
+    u
+}
+
+/// Renders one component's value for a [`CircuitGraph::to_dot`] edge
+/// label, e.g. `"100Ω"` or, for a symbolic value, its name.
+fn component_dot_label(kind: &ComponentKind) -> String {
+    match kind {
+        ComponentKind::Resistor { r } => format!("{}Ω", value_label(&r.0)),
+        ComponentKind::Inductor { l } => format!("{}H", value_label(&l.0)),
+        ComponentKind::Capacitor { c } => format!("{}F", value_label(&c.0)),
+        ComponentKind::Impedance { z } => match z {
+            ImpedanceResult::Finite(z) => format!("{z}Ω"),
+            ImpedanceResult::Open => "open".to_string(),
+            ImpedanceResult::Short => "short".to_string(),
+        },
+        ComponentKind::VoltageSource { v } => format!("{}V", v.0),
+        ComponentKind::CurrentSource { i } => format!("{}A", i.0),
+        ComponentKind::VCVS { gain, .. } => format!("E={gain}"),
+        ComponentKind::VCCS { gain, .. } => format!("G={gain}"),
+        ComponentKind::CCVS { gain, .. } => format!("H={gain}"),
+        ComponentKind::CCCS { gain, .. } => format!("F={gain}"),
+    }
+}
+
+fn value_label(value: &Value<f64>) -> String {
+    match value {
+        Value::Known(v) => format!("{v}"),
+        Value::Unknown(name) => name.clone(),
+    }
+}
+
+/// Whether two components' endpoint pairs connect the same two nodes,
+/// regardless of which one is `nodes.0` vs `nodes.1`.
+fn same_node_pair(a: (NodeIndex, NodeIndex), b: (NodeIndex, NodeIndex)) -> bool {
+    a == b || a == (b.1, b.0)
+}
+
+/// Records that `inputs` were just folded into `new_idx` by a
+/// [`CircuitGraph::reduce_series_parallel`] merge, keeping every entry
+/// already pointing at one of `inputs` (from an earlier merge further
+/// back in the chain) pointed at `new_idx` instead, so `equivalent_of`
+/// always maps an original index straight to its final survivor.
+fn record_merge(equivalent_of: &mut HashMap<ComponentIndex, ComponentIndex>, inputs: &[ComponentIndex], new_idx: ComponentIndex) {
+    for value in equivalent_of.values_mut() {
+        if inputs.contains(value) {
+            *value = new_idx;
+        }
+    }
+    for &input in inputs {
+        equivalent_of.insert(input, new_idx);
+    }
+}
+
+/// A disjoint-set structure over node indices, with path compression and
+/// union by rank, used to answer connectivity queries on a
+/// [`CircuitGraph`] without building an explicit adjacency walk.
+struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<usize>,
+}
+
+impl UnionFind {
+    fn new(n: usize) -> Self {
+        Self { parent: (0..n).collect(), rank: vec![0; n] }
+    }
+
+    fn find(&mut self, i: usize) -> usize {
+        if self.parent[i] != i {
+            self.parent[i] = self.find(self.parent[i]);
+        }
+        self.parent[i]
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (ra, rb) = (self.find(a), self.find(b));
+        if ra == rb {
+            return;
+        }
+        match self.rank[ra].cmp(&self.rank[rb]) {
+            std::cmp::Ordering::Less => self.parent[ra] = rb,
+            std::cmp::Ordering::Greater => self.parent[rb] = ra,
+            std::cmp::Ordering::Equal => {
+                self.parent[rb] = ra;
+                self.rank[ra] += 1;
+            }
+        }
+    }
 }
@@ -0,0 +1,15 @@
+pub mod analysis;
+pub mod component;
+pub mod errors;
+pub mod fft;
+pub mod grammar;
+pub mod graph;
+pub mod mna;
+pub mod netlist;
+pub mod parser;
+pub mod reduce;
+pub mod serialize;
+pub mod subckt;
+pub mod symbolic;
+pub mod transient;
+pub mod units;
@@ -0,0 +1,311 @@
+//! Modified Nodal Analysis (MNA) for arbitrary circuit topologies.
+//!
+//! `reduce::reduce` works by local series, parallel and delta-wye
+//! rewrites, so a network those rewrites cannot collapse to a single
+//! component needs a real linear solve. This module builds the complex nodal
+//! admittance matrix for a [`CircuitGraph`] and solves it directly,
+//! augmenting the system with one branch-current unknown per active
+//! voltage source, [`ComponentKind::VCVS`] and [`ComponentKind::CCVS`] in
+//! the usual MNA style. [`ComponentKind::VCCS`] and [`ComponentKind::CCCS`]
+//! need no extra unknown: they stamp a transconductance term directly into
+//! the admittance matrix instead.
+//!
+//! Each branch-current unknown is the current flowing through its
+//! component from `nodes.0` to `nodes.1`, so a voltage source driving
+//! current out of its `nodes.0` terminal into a load has a negative
+//! branch current.
+
+use crate::component::ComponentKind;
+use crate::graph::{CircuitGraph, NodeIndex};
+use crate::units::{AngularFrequency, ImpedanceResult};
+use num_complex::Complex64;
+use std::collections::HashMap;
+
+/// Impedances smaller than this in magnitude are treated as a short.
+const EPSILON_SHORT: f64 = 1e-9;
+
+/// Computes the equivalent impedance between `terminal_a` and `terminal_b`
+/// by solving the full nodal admittance system, so it works on any
+/// connected topology and not just series/parallel networks.
+///
+/// One node is grounded (the graph's declared ground, or node 0 if none
+/// is set), every active passive component is stamped into the admittance
+/// matrix, a 1 A test current is injected between the two terminals, and
+/// the resulting system is solved with complex Gaussian elimination using
+/// partial pivoting. A singular system means a floating sub-network and
+/// is reported as [`ImpedanceResult::Open`]; a zero-impedance path between
+/// the terminals is reported as [`ImpedanceResult::Short`].
+pub fn equivalent_impedance(
+    graph: &CircuitGraph,
+    omega: AngularFrequency,
+    terminal_a: NodeIndex,
+    terminal_b: NodeIndex,
+) -> ImpedanceResult {
+    if terminal_a == terminal_b {
+        return ImpedanceResult::Short;
+    }
+
+    let n = graph.nodes.len();
+    let ground = graph.ground.unwrap_or(0);
+
+    // Map every non-ground node onto a row/column of the reduced system.
+    let mut node_row: Vec<Option<usize>> = vec![None; n];
+    let mut next_row = 0usize;
+    for idx in 0..n {
+        if idx != ground {
+            node_row[idx] = Some(next_row);
+            next_row += 1;
+        }
+    }
+    let n_node_rows = next_row;
+
+    // Every component that carries its own MNA branch-current unknown:
+    // independent voltage sources plus the two dependent sources whose
+    // output is itself a voltage (VCVS/CCVS).
+    let branch_components: Vec<usize> = graph
+        .components
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| {
+            c.is_active
+                && matches!(
+                    c.kind,
+                    ComponentKind::VoltageSource { .. } | ComponentKind::VCVS { .. } | ComponentKind::CCVS { .. }
+                )
+        })
+        .map(|(idx, _)| idx)
+        .collect();
+
+    // Maps a branch-carrying component's index onto its row/column, so
+    // CCVS/CCCS can look up the control branch they sense current through.
+    let branch_row_of: HashMap<usize, usize> = branch_components
+        .iter()
+        .enumerate()
+        .map(|(branch, &idx)| (idx, n_node_rows + branch))
+        .collect();
+
+    let dim = n_node_rows + branch_components.len();
+    if dim == 0 {
+        return ImpedanceResult::Open;
+    }
+
+    let mut a = vec![vec![Complex64::new(0.0, 0.0); dim]; dim];
+    let mut rhs = vec![Complex64::new(0.0, 0.0); dim];
+
+    for comp in &graph.components {
+        if !comp.is_active
+            || matches!(
+                comp.kind,
+                ComponentKind::VoltageSource { .. }
+                    | ComponentKind::CurrentSource { .. }
+                    | ComponentKind::VCVS { .. }
+                    | ComponentKind::VCCS { .. }
+                    | ComponentKind::CCVS { .. }
+                    | ComponentKind::CCCS { .. }
+            )
+        {
+            continue;
+        }
+        let (p, q) = comp.nodes;
+        match comp.kind.impedance(omega) {
+            ImpedanceResult::Open => {}
+            ImpedanceResult::Short => stamp_admittance(&mut a, &node_row, p, q, large_admittance()),
+            ImpedanceResult::Finite(z) => {
+                if z.norm() < EPSILON_SHORT {
+                    stamp_admittance(&mut a, &node_row, p, q, large_admittance());
+                } else {
+                    stamp_admittance(&mut a, &node_row, p, q, 1.0 / z);
+                }
+            }
+        }
+    }
+
+    // VCCS/CCCS need no branch-current unknown of their own: they inject
+    // current straight into the (out-node, control-unknown) entries of the
+    // admittance matrix.
+    for comp in &graph.components {
+        if !comp.is_active {
+            continue;
+        }
+        let (p, q) = comp.nodes;
+        match &comp.kind {
+            ComponentKind::VCCS { gain, control_nodes } => {
+                let (cp, cq) = *control_nodes;
+                stamp_transconductance(&mut a, &node_row, p, q, cp, cq, Complex64::new(*gain, 0.0));
+            }
+            ComponentKind::CCCS { gain, control_component } => {
+                let control_row = branch_row_of[control_component];
+                if let Some(pi) = node_row[p] {
+                    a[pi][control_row] += Complex64::new(*gain, 0.0);
+                }
+                if let Some(qi) = node_row[q] {
+                    a[qi][control_row] -= Complex64::new(*gain, 0.0);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for (branch, &idx) in branch_components.iter().enumerate() {
+        let branch_row = n_node_rows + branch;
+        let comp = &graph.components[idx];
+        let (p, q) = comp.nodes;
+        if let Some(pi) = node_row[p] {
+            a[pi][branch_row] += Complex64::new(1.0, 0.0);
+            a[branch_row][pi] += Complex64::new(1.0, 0.0);
+        }
+        if let Some(qi) = node_row[q] {
+            a[qi][branch_row] -= Complex64::new(1.0, 0.0);
+            a[branch_row][qi] -= Complex64::new(1.0, 0.0);
+        }
+        match &comp.kind {
+            ComponentKind::VoltageSource { v } => {
+                rhs[branch_row] = (*v).into();
+            }
+            ComponentKind::VCVS { gain, control_nodes } => {
+                let (cp, cq) = *control_nodes;
+                if let Some(cpi) = node_row[cp] {
+                    a[branch_row][cpi] -= Complex64::new(*gain, 0.0);
+                }
+                if let Some(cqi) = node_row[cq] {
+                    a[branch_row][cqi] += Complex64::new(*gain, 0.0);
+                }
+            }
+            ComponentKind::CCVS { gain, control_component } => {
+                let control_row = branch_row_of[control_component];
+                a[branch_row][control_row] -= Complex64::new(*gain, 0.0);
+            }
+            _ => unreachable!("branch_components only contains VoltageSource/VCVS/CCVS"),
+        }
+    }
+
+    // Inject a 1 A test current from terminal_b into terminal_a.
+    if let Some(ai) = node_row[terminal_a] {
+        rhs[ai] += Complex64::new(1.0, 0.0);
+    }
+    if let Some(bi) = node_row[terminal_b] {
+        rhs[bi] -= Complex64::new(1.0, 0.0);
+    }
+
+    let Some(solution) = solve_complex(a, rhs) else {
+        return ImpedanceResult::Open;
+    };
+
+    let zero = Complex64::new(0.0, 0.0);
+    let v_a = node_row[terminal_a].map(|i| solution[i]).unwrap_or(zero);
+    let v_b = node_row[terminal_b].map(|i| solution[i]).unwrap_or(zero);
+    let z_eq = v_a - v_b;
+
+    if z_eq.norm() < EPSILON_SHORT {
+        ImpedanceResult::Short
+    } else {
+        ImpedanceResult::new_finite(z_eq)
+    }
+}
+
+fn large_admittance() -> Complex64 {
+    Complex64::new(1e12, 0.0)
+}
+
+/// Stamps a VCCS-style transconductance: a current `g * (V(cp) - V(cq))`
+/// flowing out of `p`, through the source, into `q`. Unlike [`stamp_admittance`] this is not
+/// symmetric in `(p, q)` vs `(cp, cq)` -- a dependent source's output
+/// branch doesn't carry the same current as its control branch.
+fn stamp_transconductance(
+    a: &mut [Vec<Complex64>],
+    node_row: &[Option<NodeIndex>],
+    p: NodeIndex,
+    q: NodeIndex,
+    cp: NodeIndex,
+    cq: NodeIndex,
+    g: Complex64,
+) {
+    let pi = node_row[p];
+    let qi = node_row[q];
+    let cpi = node_row[cp];
+    let cqi = node_row[cq];
+    if let Some(pi) = pi {
+        if let Some(cpi) = cpi {
+            a[pi][cpi] += g;
+        }
+        if let Some(cqi) = cqi {
+            a[pi][cqi] -= g;
+        }
+    }
+    if let Some(qi) = qi {
+        if let Some(cpi) = cpi {
+            a[qi][cpi] -= g;
+        }
+        if let Some(cqi) = cqi {
+            a[qi][cqi] += g;
+        }
+    }
+}
+
+fn stamp_admittance(
+    a: &mut [Vec<Complex64>],
+    node_row: &[Option<NodeIndex>],
+    p: NodeIndex,
+    q: NodeIndex,
+    y: Complex64,
+) {
+    let pi = node_row[p];
+    let qi = node_row[q];
+    if let Some(pi) = pi {
+        a[pi][pi] += y;
+    }
+    if let Some(qi) = qi {
+        a[qi][qi] += y;
+    }
+    if let (Some(pi), Some(qi)) = (pi, qi) {
+        a[pi][qi] -= y;
+        a[qi][pi] -= y;
+    }
+}
+
+/// Solves `a * x = b` by Gaussian elimination with partial pivoting.
+/// Returns `None` if `a` is singular (or numerically indistinguishable
+/// from singular), which callers interpret as a floating sub-network.
+fn solve_complex(mut a: Vec<Vec<Complex64>>, mut b: Vec<Complex64>) -> Option<Vec<Complex64>> {
+    let n = b.len();
+    for col in 0..n {
+        let mut pivot_row = col;
+        let mut pivot_mag = a[col][col].norm();
+        for row in (col + 1)..n {
+            let mag = a[row][col].norm();
+            if mag > pivot_mag {
+                pivot_row = row;
+                pivot_mag = mag;
+            }
+        }
+        if pivot_mag < 1e-12 {
+            return None;
+        }
+        if pivot_row != col {
+            a.swap(pivot_row, col);
+            b.swap(pivot_row, col);
+        }
+
+        let pivot_val = a[col][col];
+        for row in (col + 1)..n {
+            let factor = a[row][col] / pivot_val;
+            if factor == Complex64::new(0.0, 0.0) {
+                continue;
+            }
+            for k in col..n {
+                a[row][k] -= factor * a[col][k];
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+
+    let mut x = vec![Complex64::new(0.0, 0.0); n];
+    for row in (0..n).rev() {
+        let mut sum = b[row];
+        for k in (row + 1)..n {
+            sum -= a[row][k] * x[k];
+        }
+        x[row] = sum / a[row][row];
+    }
+    Some(x)
+}
@@ -0,0 +1,6 @@
+//! Public entry point for reading and writing SPICE-style netlists.
+//!
+//! The parsing and rendering logic lives in [`crate::parser`]; this module
+//! just re-exports it under the names callers reach for.
+
+pub use crate::parser::{parse_netlist as parse, to_netlist};
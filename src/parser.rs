@@ -1,11 +1,17 @@
+use num_complex::Complex64;
 use crate::errors::ParseError;
+use crate::grammar::{self, Pair, Rule};
 use crate::graph::{CircuitGraph, NodeIndex};
 use crate::component::{ComponentKind};
-use crate::units::{Voltage, Current, Resistance, Inductance, Capacitance};
+use crate::units::{Voltage, Current, Resistance, Inductance, Capacitance, Value};
 use std::collections::HashMap;
 use std::fs;
 
 pub fn parse_value(input: &str, line_num: usize) -> Result<f64, ParseError> {
+    parse_value_at(input, line_num, 0)
+}
+
+fn parse_value_at(input: &str, line_num: usize, column: usize) -> Result<f64, ParseError> {
     let input = input.trim();
 
     let suffix_start = input
@@ -16,23 +22,44 @@ pub fn parse_value(input: &str, line_num: usize) -> Result<f64, ParseError> {
     let suffix = &input[suffix_start..].trim();
 
     let value: f64 = numeric_part.parse()
-        .map_err(|_| ParseError { line: line_num, message: format!("Invalid character: {numeric_part}") })?;
+        .map_err(|_| ParseError { line: line_num, column, message: format!("Invalid character: {numeric_part}") })?;
 
-    let multiplier = match suffix.to_lowercase().as_str() {
-        "t" => 1e12,
-        "g" => 1e9,
-        "meg" => 1e6,
-        "k" => 1e3,
-        "m" => 1e-3,
-        "u" | "Âµ" => 1e-6,
-        "n" => 1e-9,
-        "p" => 1e-12,
+    // Case matters here: "M" is mega (1e6) while "m" is milli (1e-3), the
+    // one place these engineering suffixes collide.
+    let multiplier = match *suffix {
         "" => 1.0,
-        _ => return Err(ParseError { line: line_num, message: format!("Invalid suffix: {suffix}") }),
+        "T" | "t" => 1e12,
+        "G" | "g" => 1e9,
+        "M" => 1e6,
+        "k" | "K" => 1e3,
+        "m" => 1e-3,
+        "u" | "U" | "\u{b5}" => 1e-6,
+        "n" | "N" => 1e-9,
+        "p" | "P" => 1e-12,
+        _ => return Err(ParseError { line: line_num, column, message: format!("Invalid suffix: {suffix}") }),
     };
     Ok(value * multiplier)
 }
 
+/// Parses a raw value token into a [`Value<f64>`], supporting ElectroSolve's
+/// symbolic workflows: a token that doesn't start like a number (e.g. `Rfoo`)
+/// is treated as a bare identifier naming an unknown quantity rather than a
+/// parse error.
+pub fn parse_element_value(input: &str, line_num: usize) -> Result<Value<f64>, ParseError> {
+    parse_element_value_at(input, line_num, 0)
+}
+
+fn parse_element_value_at(input: &str, line_num: usize, column: usize) -> Result<Value<f64>, ParseError> {
+    let trimmed = input.trim();
+    match trimmed.chars().next() {
+        Some(c) if c.is_ascii_digit() || c == '.' || c == '-' => {
+            parse_value_at(trimmed, line_num, column).map(Value::Known)
+        }
+        Some(_) => Ok(Value::Unknown(trimmed.to_string())),
+        None => Err(ParseError { line: line_num, column, message: "Empty value".to_string() }),
+    }
+}
+
 pub fn get_or_create_node(name: &str, graph: &mut CircuitGraph, node_map: &mut HashMap<String, NodeIndex>) -> NodeIndex {
     if let Some(idx) = node_map.get(name) {
         return *idx;
@@ -43,48 +70,95 @@ pub fn get_or_create_node(name: &str, graph: &mut CircuitGraph, node_map: &mut H
     idx
 }
 
+/// Parses one already-joined logical line (see [`grammar::logical_lines`])
+/// against the netlist grammar and, for a `component` line, lowers it
+/// into the graph. Comment and directive lines are recognized but are
+/// no-ops here: a directive like `.end` affects how a SPICE deck is
+/// divided into sections, not the graph ElectroSolve builds from it.
 pub fn parse_component_line(
-    line: &str, 
-    line_num: usize, 
+    line: &str,
+    line_num: usize,
     graph: &mut CircuitGraph,
     node_map: &mut HashMap<String, NodeIndex>
 ) -> Result<(), ParseError> {
-    let line = line.trim();
-    if line.is_empty() || line.starts_with('*') {
-        return Ok(());
-    }
-    let tokens: Vec<&str> = line.split_whitespace().collect();
-    if tokens.len() < 4 {
-        return Err(ParseError { line: line_num, message: format!("Expected at least 4 tokens, got {tokens:?}") });
+    let pair = grammar::parse_line(line)
+        .map_err(|e| ParseError { line: line_num, column: e.column, message: e.message })?;
+
+    match pair.rule {
+        Rule::Comment | Rule::Directive => Ok(()),
+        Rule::Component => lower_component(&pair, line_num, graph, node_map),
+        Rule::Instance => Err(ParseError {
+            line: line_num,
+            column: pair.column,
+            message: "subcircuit instance was not flattened before parsing".to_string(),
+        }),
+        other => unreachable!("grammar::parse_line never returns a bare {other:?}"),
     }
-    let component_id = tokens[0];
+}
+
+fn lower_component(
+    pair: &Pair<'_>,
+    line_num: usize,
+    graph: &mut CircuitGraph,
+    node_map: &mut HashMap<String, NodeIndex>,
+) -> Result<(), ParseError> {
+    let component_id_pair = pair.children_of(Rule::ComponentId).next()
+        .expect("a Component pair always has a ComponentId child");
+    let component_id = component_id_pair.text;
     let first_char = component_id.chars().next()
-        .ok_or_else(|| ParseError { line: line_num, message: format!("Invalid component ID: {component_id}") })?;
+        .ok_or_else(|| ParseError { line: line_num, column: component_id_pair.column, message: format!("Invalid component ID: {component_id}") })?;
 
-    let node1_name = tokens[1];
-    let node2_name = tokens[2];
+    let node_refs: Vec<&Pair<'_>> = pair.children_of(Rule::NodeRef).collect();
+    let node1_name = node_refs[0].text;
+    let node2_name = node_refs[1].text;
 
     let node1_idx = get_or_create_node(node1_name, graph, node_map);
     let node2_idx = get_or_create_node(node2_name, graph, node_map);
-    let value = parse_value(tokens[3], line_num)?;
+
+    let values: Vec<&Pair<'_>> = pair.children_of(Rule::Value).collect();
+    let value_at = |i: usize| -> Result<&Pair<'_>, ParseError> {
+        values.get(i).copied().ok_or_else(|| ParseError {
+            line: line_num,
+            column: pair.column,
+            message: format!("{component_id}: expected at least {} value(s), got {}", i + 1, values.len()),
+        })
+    };
 
     let kind = match first_char {
-        'R' => ComponentKind::Resistor { r: Resistance::known(value)? },
-        'L' => ComponentKind::Inductor { l: Inductance::known(value)? },
-        'C' => ComponentKind::Capacitor { c: Capacitance::known(value)? },
+        'R' => {
+            let v = value_at(0)?;
+            ComponentKind::Resistor { r: match parse_element_value_at(v.text, line_num, v.column)? {
+                Value::Known(val) => Resistance::known(val)?,
+                Value::Unknown(name) => Resistance::unknown(name),
+            }}
+        }
+        'L' => {
+            let v = value_at(0)?;
+            ComponentKind::Inductor { l: match parse_element_value_at(v.text, line_num, v.column)? {
+                Value::Known(val) => Inductance::known(val)?,
+                Value::Unknown(name) => Inductance::unknown(name),
+            }}
+        }
+        'C' => {
+            let v = value_at(0)?;
+            ComponentKind::Capacitor { c: match parse_element_value_at(v.text, line_num, v.column)? {
+                Value::Known(val) => Capacitance::known(val)?,
+                Value::Unknown(name) => Capacitance::unknown(name),
+            }}
+        }
         'V' => {
+            let v = value_at(0)?;
+            let value = parse_value_at(v.text, line_num, v.column)?;
             // Check for AC/DC specification
-            let v = if tokens.len() >= 5 && tokens[4].to_uppercase() == "AC" {
+            let v = if values.len() >= 2 && values[1].text.eq_ignore_ascii_case("AC") {
                 // AC voltage: V1 N1 0 AC <magnitude> <phase>
-                let magnitude = if tokens.len() >= 6 {
-                    parse_value(tokens[5],  line_num)?
-                } else {
-                    value  // Use same value as default
+                let magnitude = match values.get(2) {
+                    Some(m) => parse_value_at(m.text, line_num, m.column)?,
+                    None => value, // Use same value as default
                 };
-                let phase = if tokens.len() >= 7 {
-                    parse_value(tokens[6],  line_num)?
-                } else {
-                    0.0
+                let phase = match values.get(3) {
+                    Some(p) => parse_value_at(p.text, line_num, p.column)?,
+                    None => 0.0,
                 };
                 Voltage::ac_phasor(magnitude, phase)
             } else {
@@ -94,18 +168,18 @@ pub fn parse_component_line(
             ComponentKind::VoltageSource { v }
         }
         'I' => {
+            let v = value_at(0)?;
+            let value = parse_value_at(v.text, line_num, v.column)?;
             // Check for AC/DC specification
-            let i = if tokens.len() >= 5 && tokens[4].to_uppercase() == "AC" {
+            let i = if values.len() >= 2 && values[1].text.eq_ignore_ascii_case("AC") {
                 // AC current: I1 N1 0 AC <magnitude> <phase>
-                let magnitude = if tokens.len() >= 6 {
-                    parse_value(tokens[5],  line_num)?
-                } else {
-                    value  // Use same value as default
+                let magnitude = match values.get(2) {
+                    Some(m) => parse_value_at(m.text, line_num, m.column)?,
+                    None => value, // Use same value as default
                 };
-                let phase = if tokens.len() >= 7 {
-                    parse_value(tokens[6], line_num)?
-                } else {
-                    0.0
+                let phase = match values.get(3) {
+                    Some(p) => parse_value_at(p.text, line_num, p.column)?,
+                    None => 0.0,
                 };
                 Current::ac_phasor(magnitude, phase)
             } else {
@@ -114,7 +188,61 @@ pub fn parse_component_line(
             };
             ComponentKind::CurrentSource { i }
         },
-        _ => return Err(ParseError { line: line_num, message: format!("Unknown component type: {first_char}") }),
+        'E' | 'G' => {
+            // Exxx/Gxxx n+ n- nc+ nc- gain
+            let control_p_name = value_at(0)?.text;
+            let control_q_name = value_at(1)?.text;
+            let gain_v = value_at(2)?;
+            let gain = parse_value_at(gain_v.text, line_num, gain_v.column)?;
+            let control_nodes = (
+                get_or_create_node(control_p_name, graph, node_map),
+                get_or_create_node(control_q_name, graph, node_map),
+            );
+            if first_char == 'E' {
+                ComponentKind::VCVS { gain, control_nodes }
+            } else {
+                ComponentKind::VCCS { gain, control_nodes }
+            }
+        }
+        'H' | 'F' => {
+            // Hxxx/Fxxx n+ n- <controlling source id> gain
+            let control_id = value_at(0)?;
+            let gain_v = value_at(1)?;
+            let gain = parse_value_at(gain_v.text, line_num, gain_v.column)?;
+            let control_component = graph
+                .components
+                .iter()
+                .position(|c| c.id == control_id.text)
+                .ok_or_else(|| ParseError {
+                    line: line_num,
+                    column: control_id.column,
+                    message: format!("unknown controlling source: {}", control_id.text),
+                })?;
+            // The sensed current only exists as an MNA unknown for
+            // components that carry their own branch-current variable --
+            // otherwise `mna::equivalent_impedance` would index
+            // `branch_row_of` with a component that was never inserted
+            // into it and panic instead of reporting a sane error here.
+            if !matches!(
+                graph.components[control_component].kind,
+                ComponentKind::VoltageSource { .. } | ComponentKind::VCVS { .. } | ComponentKind::CCVS { .. }
+            ) {
+                return Err(ParseError {
+                    line: line_num,
+                    column: control_id.column,
+                    message: format!(
+                        "{} does not carry a branch current and cannot control a current-controlled source: {}",
+                        control_id.text, component_id
+                    ),
+                });
+            }
+            if first_char == 'H' {
+                ComponentKind::CCVS { gain, control_component }
+            } else {
+                ComponentKind::CCCS { gain, control_component }
+            }
+        }
+        _ => return Err(ParseError { line: line_num, column: component_id_pair.column, message: format!("Unknown component type: {first_char}") }),
     };
     graph.add_component(component_id.to_string(), kind, (node1_idx, node2_idx));
     if node1_name.to_lowercase() == "gnd" || node1_name == "0" {
@@ -129,23 +257,80 @@ pub fn parse_component_line(
 pub fn parse_netlist(input: &str) -> Result<CircuitGraph, ParseError> {
     let mut graph = CircuitGraph::new();
     let mut node_map = HashMap::new();
-    for (line_num, line) in input.lines().enumerate() {
-        let line_num = line_num + 1;
-        if let Err(e) = parse_component_line(line, line_num, &mut graph, &mut node_map) {
-            return Err(e);
-        }   
+    let lines = crate::subckt::flatten(grammar::logical_lines(input))?;
+    for (line_num, line) in lines {
+        parse_component_line(&line, line_num, &mut graph, &mut node_map)?;
     }
 
     if graph.ground.is_none() {
-        return Err(ParseError { line: 0, message: "No ground node specified".to_string() });
+        return Err(ParseError { line: 0, column: 0, message: "No ground node specified".to_string() });
     }
     if graph.components.is_empty() {
-        return Err(ParseError { line: 0, message: "No components specified".to_string() });
+        return Err(ParseError { line: 0, column: 0, message: "No components specified".to_string() });
     }
     Ok(graph)
 }
 
 pub fn parse_file(path: &str) -> Result<CircuitGraph, ParseError> {
-    let contents = fs::read_to_string(path).map_err(|_| ParseError { line: 0, message: format!("Failed to read file: {path}") })?;
+    let contents = fs::read_to_string(path).map_err(|_| ParseError { line: 0, column: 0, message: format!("Failed to read file: {path}") })?;
     parse_netlist(&contents)
 }
+
+/// Renders a [`CircuitGraph`] back into SPICE-style netlist text, so a
+/// parsed-then-reduced circuit can be round-tripped. Ground is always
+/// written as node `0`, regardless of the name the node was originally
+/// parsed with. `ComponentKind::Impedance` (an already-reduced equivalent,
+/// not a primitive SPICE element) has no netlist syntax and is omitted.
+pub fn to_netlist(graph: &CircuitGraph) -> String {
+    let mut lines = Vec::with_capacity(graph.components.len() + 1);
+    for comp in &graph.components {
+        let node1 = node_ref(graph, comp.nodes.0);
+        let node2 = node_ref(graph, comp.nodes.1);
+        let rest: Vec<String> = match &comp.kind {
+            ComponentKind::Resistor { r } => vec![value_ref(&r.0)],
+            ComponentKind::Inductor { l } => vec![value_ref(&l.0)],
+            ComponentKind::Capacitor { c } => vec![value_ref(&c.0)],
+            ComponentKind::VoltageSource { v } => phasor_ref(v.0),
+            ComponentKind::CurrentSource { i } => phasor_ref(i.0),
+            ComponentKind::VCVS { gain, control_nodes } | ComponentKind::VCCS { gain, control_nodes } => {
+                vec![node_ref(graph, control_nodes.0), node_ref(graph, control_nodes.1), format!("{gain}")]
+            }
+            ComponentKind::CCVS { gain, control_component } | ComponentKind::CCCS { gain, control_component } => {
+                vec![graph.components[*control_component].id.clone(), format!("{gain}")]
+            }
+            ComponentKind::Impedance { .. } => continue,
+        };
+        lines.push(format!("{} {} {} {}", comp.id, node1, node2, rest.join(" ")));
+    }
+    lines.push(".end".to_string());
+    lines.join("\n")
+}
+
+fn node_ref(graph: &CircuitGraph, idx: NodeIndex) -> String {
+    if graph.is_ground(idx) {
+        "0".to_string()
+    } else {
+        graph.node(idx).id.clone()
+    }
+}
+
+fn value_ref(value: &Value<f64>) -> String {
+    match value {
+        Value::Known(v) => format!("{v}"),
+        Value::Unknown(name) => name.clone(),
+    }
+}
+
+/// Renders a voltage/current phasor back into SPICE-style value tokens: a
+/// bare magnitude for a purely-real (DC) value, or `<magnitude> AC
+/// <magnitude> <phase>` when it carries an imaginary part, matching the
+/// `'V'`/`'I'` AC syntax `lower_component` parses above.
+fn phasor_ref(z: Complex64) -> Vec<String> {
+    if z.im != 0.0 {
+        let magnitude = z.norm();
+        let phase_degrees = z.im.atan2(z.re).to_degrees();
+        vec![format!("{magnitude}"), "AC".to_string(), format!("{magnitude}"), format!("{phase_degrees}")]
+    } else {
+        vec![format!("{}", z.re)]
+    }
+}
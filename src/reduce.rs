@@ -1,10 +1,13 @@
 use num_complex::Complex64;
-use crate::graph::{CircuitGraph, ComponentIndex, NodeIndex};
+use crate::graph::{CircuitComponent, CircuitGraph, ComponentIndex, NodeIndex};
 use std::collections::HashMap;
-use crate::units::AngularFrequency;
+use crate::mna;
+use crate::units::{AngularFrequency, ImpedanceResult};
 use crate::component::impedance_to_kind;
 use crate::errors::CircuitError;
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ReductionStep {
     Series{
         components: Vec<ComponentIndex>,
@@ -19,21 +22,48 @@ pub enum ReductionStep {
     DeltaWye {
         delta_nodes: (NodeIndex, NodeIndex, NodeIndex),
         wye_node: NodeIndex,
+    },
+    DeltaToWye {
+        triangle_nodes: (NodeIndex, NodeIndex, NodeIndex),
+        triangle_components: (ComponentIndex, ComponentIndex, ComponentIndex),
+        new_node: NodeIndex,
     }
 }
 
 
+/// Repeatedly applies series, parallel and delta-wye reductions until
+/// none apply any more, returning the trace of steps taken. Only
+/// collapses series/parallel/delta-wye structure -- a topology that's
+/// still stuck with more than one active component afterwards needs a
+/// real nodal solve, which [`reduce_with_mna_fallback`] provides.
 pub fn reduce(graph: &mut CircuitGraph, omega: AngularFrequency) -> Result<Vec<ReductionStep>, CircuitError> {
     let mut steps = Vec::new();
     graph.cache_impedances(omega);
     loop {
         if let Some(mut step) = find_series_reduction(graph) {
-            apply_reduction(graph, &mut step);
+            apply_reduction(graph, &mut step)?;
             steps.push(step);
             continue;
         }
         if let Some(mut step) = find_parallel_reduction(graph) {
-            apply_reduction(graph, &mut step);
+            apply_reduction(graph, &mut step)?;
+            steps.push(step);
+            continue;
+        }
+        // Series/parallel alone gets stuck on bridge and other
+        // non-series-parallel topologies; try a delta-wye transform
+        // before giving up so those networks keep reducing.
+        if let Some(mut step) = find_delta_wye_reduction(graph) {
+            apply_reduction(graph, &mut step)?;
+            graph.cache_impedances(omega);
+            steps.push(step);
+            continue;
+        }
+        // The dual direction: a triangle with no series/parallel/wye move
+        // available can still unblock progress by becoming a star.
+        if let Some(mut step) = find_delta_to_wye_reduction(graph) {
+            apply_reduction(graph, &mut step)?;
+            graph.cache_impedances(omega);
             steps.push(step);
             continue;
         }
@@ -42,41 +72,181 @@ pub fn reduce(graph: &mut CircuitGraph, omega: AngularFrequency) -> Result<Vec<R
     Ok(steps)
 }
 
+/// Like [`reduce`], but when the algebraic passes above get stuck with
+/// more than one active component remaining (a topology with a genuine
+/// mesh, beyond what series/parallel/delta-wye can unwind), falls back
+/// to [`mna::equivalent_impedance`] between `terminal_a` and
+/// `terminal_b` and reports that as the final answer instead of
+/// whatever partially-reduced impedance happens to be left active.
+/// Returns the reduction trace recorded before the fallback kicked in
+/// (empty if MNA ran without any algebraic progress at all) together
+/// with the two-terminal equivalent impedance.
+pub fn reduce_with_mna_fallback(
+    graph: &mut CircuitGraph,
+    omega: AngularFrequency,
+    terminal_a: NodeIndex,
+    terminal_b: NodeIndex,
+) -> Result<(Vec<ReductionStep>, ImpedanceResult), CircuitError> {
+    let steps = reduce(graph, omega)?;
+    let z = match graph.components.iter().find(|c| c.is_active) {
+        Some(comp) if graph.active_component_count() == 1 => comp
+            .cached_impedance
+            .clone()
+            .ok_or(CircuitError::InvalidImpedance(Complex64::new(0.0, 0.0)))?,
+        _ => mna::equivalent_impedance(graph, omega, terminal_a, terminal_b),
+    };
+    Ok((steps, z))
+}
+
+/// Finds a wye (star) of three passive components meeting at a single
+/// interior, non-ground node and returns the step that would rewrite it
+/// into the equivalent delta across its three neighbors.
+fn find_delta_wye_reduction(graph: &CircuitGraph) -> Option<ReductionStep> {
+    for node_idx in 0..graph.nodes.len() {
+        if graph.is_ground(node_idx) {
+            continue;
+        }
+        let connected = graph.connections_at(node_idx);
+        if connected.len() != 3 {
+            continue;
+        }
+        if connected.iter().any(|&idx| {
+            let comp = &graph.components[idx];
+            !comp.kind.is_passive() || comp.cached_impedance.is_none()
+        }) {
+            continue;
+        }
+
+        let neighbors: Vec<NodeIndex> = connected
+            .iter()
+            .map(|&idx| other_node(&graph.components[idx], node_idx))
+            .collect();
+        if neighbors[0] == neighbors[1] || neighbors[1] == neighbors[2] || neighbors[0] == neighbors[2] {
+            // A neighbor appearing twice means this is really a parallel
+            // pair plus a pendant branch, not a clean wye; let the
+            // parallel rule handle it instead.
+            continue;
+        }
+
+        return Some(ReductionStep::DeltaWye {
+            delta_nodes: (neighbors[0], neighbors[1], neighbors[2]),
+            wye_node: node_idx,
+        });
+    }
+    None
+}
+
+fn other_node(comp: &CircuitComponent, from: NodeIndex) -> NodeIndex {
+    if comp.nodes.0 == from { comp.nodes.1 } else { comp.nodes.0 }
+}
+
+fn connects(comp: &CircuitComponent, x: NodeIndex, y: NodeIndex) -> bool {
+    (comp.nodes.0 == x && comp.nodes.1 == y) || (comp.nodes.0 == y && comp.nodes.1 == x)
+}
+
+/// Finds a delta (triangle) of three passive components connecting three
+/// distinct, non-ground nodes A-B-C and returns the step that would
+/// collapse it into an equivalent wye through a fresh center node.
+///
+/// Unlike `find_delta_wye_reduction`, a bare triangle with nothing else
+/// attached is left alone: converting it would just hand
+/// `find_delta_wye_reduction` a fresh degree-3 wye to immediately convert
+/// right back, looping forever. Requiring that at least one corner has a
+/// branch beyond the triangle guarantees the transform actually frees up
+/// a series reduction at that corner on the next pass.
+fn find_delta_to_wye_reduction(graph: &CircuitGraph) -> Option<ReductionStep> {
+    for idx_ab in 0..graph.components.len() {
+        let comp_ab = &graph.components[idx_ab];
+        if !comp_ab.is_active || !comp_ab.kind.is_passive() {
+            continue;
+        }
+        let (node_a, node_b) = comp_ab.nodes;
+
+        for &idx_bc in &graph.connections_at(node_b) {
+            if idx_bc == idx_ab {
+                continue;
+            }
+            let comp_bc = &graph.components[idx_bc];
+            if !comp_bc.kind.is_passive() {
+                continue;
+            }
+            let node_c = other_node(comp_bc, node_b);
+            if node_c == node_a {
+                continue;
+            }
+
+            let idx_ca = match graph
+                .connections_at(node_c)
+                .into_iter()
+                .find(|&idx| idx != idx_ab && idx != idx_bc && graph.components[idx].kind.is_passive() && connects(&graph.components[idx], node_c, node_a))
+            {
+                Some(idx) => idx,
+                None => continue,
+            };
+
+            if graph.is_ground(node_a) || graph.is_ground(node_b) || graph.is_ground(node_c) {
+                continue;
+            }
+            if [idx_ab, idx_bc, idx_ca].iter().any(|&idx| graph.components[idx].cached_impedance.is_none()) {
+                continue;
+            }
+            // Open/Short branches unblock progress on their own (see
+            // `apply_reduction`'s DeltaToWye arm), so only the ordinary
+            // all-finite case needs the "does this actually help"
+            // degree check below.
+            let all_finite = [idx_ab, idx_bc, idx_ca]
+                .iter()
+                .all(|&idx| matches!(graph.components[idx].cached_impedance, Some(ImpedanceResult::Finite(_))));
+            if all_finite
+                && graph.get_node_degree(node_a) <= 2
+                && graph.get_node_degree(node_b) <= 2
+                && graph.get_node_degree(node_c) <= 2
+            {
+                continue;
+            }
+
+            return Some(ReductionStep::DeltaToWye {
+                triangle_nodes: (node_a, node_b, node_c),
+                triangle_components: (idx_ab, idx_bc, idx_ca),
+                new_node: usize::MAX,
+            });
+        }
+    }
+    None
+}
+
 fn find_series_reduction(graph: &CircuitGraph) -> Option<ReductionStep> {
     for node_idx in 0..graph.nodes.len() {
-        let node = &graph.nodes[node_idx];
-        
-        if node.degree != 2 {
+        if graph.get_node_degree(node_idx) != 2 {
             continue;
         }
-        
+
         let connected = graph.connections_at(node_idx);
-        
+
         if connected.len() != 2 {
             continue;
         }
-        
+
         let comp1_idx = connected[0];
         let comp2_idx = connected[1];
-        
+
         let comp1 = &graph.components[comp1_idx];
         let comp2 = &graph.components[comp2_idx];
-        
+
         if !comp1.kind.is_passive() || !comp2.kind.is_passive() {
             continue;
         }
-        
-        if comp1.cached_impedance == Complex64::new(0.0, 0.0) ||
-           comp2.cached_impedance == Complex64::new(0.0, 0.0) {
+
+        let (Some(ImpedanceResult::Finite(z1)), Some(ImpedanceResult::Finite(z2))) =
+            (&comp1.cached_impedance, &comp2.cached_impedance)
+        else {
             continue;
-        }
-        
-        let z_eq = comp1.cached_impedance + comp2.cached_impedance;
-        
+        };
+
         return Some(ReductionStep::Series {
             components: vec![comp1_idx, comp2_idx],
             equivalent: 0,
-            impedance: z_eq,
+            impedance: z1 + z2,
         });
     }
     None
@@ -85,16 +255,19 @@ fn find_series_reduction(graph: &CircuitGraph) -> Option<ReductionStep> {
 fn find_parallel_reduction(graph: &CircuitGraph) -> Option<ReductionStep> {
     let mut parallel_groups: HashMap<(NodeIndex, NodeIndex), Vec<ComponentIndex>> = HashMap::new();
     for (idx, comp) in graph.components.iter().enumerate() {
-        if !comp.is_active || !comp.kind.is_passive() {
+        if !comp.is_active
+            || !comp.kind.is_passive()
+            || !matches!(comp.cached_impedance, Some(ImpedanceResult::Finite(_)))
+        {
             continue;
         }
-    
+
         let key = if comp.nodes.0 < comp.nodes.1 {
             comp.nodes
         } else {
             (comp.nodes.1, comp.nodes.0)
         };
-    
+
         parallel_groups.entry(key).or_default().push(idx);
     }
 
@@ -102,25 +275,24 @@ fn find_parallel_reduction(graph: &CircuitGraph) -> Option<ReductionStep> {
         .into_iter()
         .filter(|(_, indices)| indices.len() > 1)
         .max_by_key(|(_, indices)| indices.len())?;
-    
+
     // Then calculate impedance using best_indices
     let mut admittance_sum = Complex64::new(0.0, 0.0);
     for &idx in &best_indices {
-        let z = graph.components[idx].cached_impedance;
-        if z == Complex64::new(0.0, 0.0) {
-            continue;
-        }
+        let Some(ImpedanceResult::Finite(z)) = graph.components[idx].cached_impedance else {
+            unreachable!("every candidate was filtered to a finite cached impedance above");
+        };
         admittance_sum += 1.0 / z;
     }
     let z_eq = 1.0 / admittance_sum;
-    return Some(ReductionStep::Parallel {
+    Some(ReductionStep::Parallel {
         components: best_indices,
         equivalent: 0,
         impedance: z_eq,
-    });
+    })
 }
 
-fn apply_reduction(graph: &mut CircuitGraph, step: &mut ReductionStep) -> Result<ComponentIndex, CircuitError> {
+pub(crate) fn apply_reduction(graph: &mut CircuitGraph, step: &mut ReductionStep) -> Result<ComponentIndex, CircuitError> {
     match step {
         ReductionStep::Series { components, impedance, equivalent } => {
             let comp1 = &graph.components[components[0]];
@@ -136,12 +308,9 @@ fn apply_reduction(graph: &mut CircuitGraph, step: &mut ReductionStep) -> Result
             
             for comp_idx in components {
                 graph.components[*comp_idx].is_active = false;
-                let nodes = graph.components[*comp_idx].nodes;
-                graph.nodes[nodes.0].degree -= 1;
-                graph.nodes[nodes.1].degree -= 1;
             }
-            
-            let kind = impedance_to_kind(*impedance)?;
+
+            let kind = impedance_to_kind(ImpedanceResult::new_finite(*impedance))?;
             let new_comp_idx = graph.add_component("EQ".to_string(), kind, (outer1, outer2));
             
             *equivalent = new_comp_idx;
@@ -154,18 +323,191 @@ fn apply_reduction(graph: &mut CircuitGraph, step: &mut ReductionStep) -> Result
             
             for comp_idx in components {
                 graph.components[*comp_idx].is_active = false;
-                let c_nodes = graph.components[*comp_idx].nodes;
-                graph.nodes[c_nodes.0].degree -= 1;
-                graph.nodes[c_nodes.1].degree -= 1;
             }
-            
-            let kind = impedance_to_kind(*impedance)?;
+
+            let kind = impedance_to_kind(ImpedanceResult::new_finite(*impedance))?;
             let new_comp_idx = graph.add_component("EQ".to_string(), kind, nodes);
             
             *equivalent = new_comp_idx;
             Ok(new_comp_idx)
         }
         
-        _ => panic!("apply_reduction called with unimplemented reduction type")
+        ReductionStep::DeltaWye { delta_nodes, wye_node } => {
+            let (node_a, node_b, node_c) = *delta_nodes;
+            let wye = *wye_node;
+            let branch_components = graph.connections_at(wye);
+
+            let mut impedance_at = HashMap::new();
+            for &comp_idx in &branch_components {
+                let comp = &graph.components[comp_idx];
+                let neighbor = other_node(comp, wye);
+                let z_val = comp
+                    .cached_impedance
+                    .clone()
+                    .ok_or(CircuitError::InvalidImpedance(Complex64::new(0.0, 0.0)))?;
+                impedance_at.insert(neighbor, z_val);
+            }
+
+            for &comp_idx in &branch_components {
+                graph.components[comp_idx].is_active = false;
+            }
+
+            apply_delta_wye(
+                graph,
+                [
+                    (node_a, impedance_at.remove(&node_a).expect("every delta corner has a branch")),
+                    (node_b, impedance_at.remove(&node_b).expect("every delta corner has a branch")),
+                    (node_c, impedance_at.remove(&node_c).expect("every delta corner has a branch")),
+                ],
+            )
+        }
+
+        ReductionStep::DeltaToWye { triangle_nodes, triangle_components, new_node } => {
+            let (node_a, node_b, node_c) = *triangle_nodes;
+            let (idx_ab, idx_bc, idx_ca) = *triangle_components;
+
+            let impedance_of = |idx: ComponentIndex| {
+                graph.components[idx]
+                    .cached_impedance
+                    .clone()
+                    .ok_or(CircuitError::InvalidImpedance(Complex64::new(0.0, 0.0)))
+            };
+            let z_ab = impedance_of(idx_ab)?;
+            let z_bc = impedance_of(idx_bc)?;
+            let z_ca = impedance_of(idx_ca)?;
+
+            // Each edge is paired with the node not on it (its "opposite"
+            // corner) and the two edges that share that opposite corner --
+            // exactly what the Open/Short degenerate cases below need.
+            let edges = [
+                (idx_ab, node_a, node_b, node_c, z_ab),
+                (idx_bc, node_b, node_c, node_a, z_bc),
+                (idx_ca, node_c, node_a, node_b, z_ca),
+            ];
+            let degenerate: Vec<usize> = (0..3).filter(|&i| !edges[i].4.is_finite()).collect();
+
+            match degenerate.as_slice() {
+                [] => {
+                    let z_ab = as_finite(&edges[0].4)?;
+                    let z_bc = as_finite(&edges[1].4)?;
+                    let z_ca = as_finite(&edges[2].4)?;
+                    let sum = z_ab + z_bc + z_ca;
+
+                    graph.components[idx_ab].is_active = false;
+                    graph.components[idx_bc].is_active = false;
+                    graph.components[idx_ca].is_active = false;
+
+                    let center = graph.add_node("WYE".to_string());
+
+                    let kind_a = impedance_to_kind(ImpedanceResult::new_finite(z_ab * z_ca / sum))?;
+                    let kind_b = impedance_to_kind(ImpedanceResult::new_finite(z_ab * z_bc / sum))?;
+                    let kind_c = impedance_to_kind(ImpedanceResult::new_finite(z_bc * z_ca / sum))?;
+
+                    graph.add_component("EQ_a".to_string(), kind_a, (center, node_a));
+                    graph.add_component("EQ_b".to_string(), kind_b, (center, node_b));
+                    let new_comp_idx = graph.add_component("EQ_c".to_string(), kind_c, (center, node_c));
+
+                    *new_node = center;
+                    Ok(new_comp_idx)
+                }
+                [i] => {
+                    let (deg_idx, u, _v, w, z_deg) = edges[*i].clone();
+                    let (other1_idx, .., z1) = edges[(*i + 1) % 3].clone();
+                    let (other2_idx, .., z2) = edges[(*i + 2) % 3].clone();
+                    let z1 = as_finite(&z1)?;
+                    let z2 = as_finite(&z2)?;
+
+                    match z_deg {
+                        ImpedanceResult::Open => {
+                            // `u`-`v` carries no current; the other two
+                            // edges already form the only real path
+                            // between them, through `w` -- nothing to
+                            // rewrite beyond dropping the dead edge.
+                            graph.components[deg_idx].is_active = false;
+                            *new_node = w;
+                            Ok(other1_idx)
+                        }
+                        ImpedanceResult::Short => {
+                            // `u` and `v` are the same electrical point
+                            // (the degenerate edge is a plain wire between
+                            // them), so the other two edges -- both
+                            // touching `w` -- are now in parallel between
+                            // `w` and that merged point.
+                            graph.components[other1_idx].is_active = false;
+                            graph.components[other2_idx].is_active = false;
+                            let kind = impedance_to_kind(crate::units::combine_parallel(
+                                ImpedanceResult::new_finite(z1),
+                                ImpedanceResult::new_finite(z2),
+                            ))?;
+                            *new_node = u;
+                            Ok(graph.add_component("EQ".to_string(), kind, (w, u)))
+                        }
+                        ImpedanceResult::Finite(_) => unreachable!("filtered out of `degenerate` above"),
+                    }
+                }
+                _ => Err(CircuitError::InvalidImpedance(Complex64::new(0.0, 0.0))),
+            }
+        }
+    }
+}
+
+/// Rewrites a wye's three branch impedances into the equivalent delta
+/// across its three neighbors, handling a degenerate `Open`/`Short`
+/// branch the way the physical Y-Δ transform degenerates in the
+/// limit: an open branch leaves the other two simply in series between
+/// their neighbors (the wye center isn't really connected to the open
+/// branch's neighbor at all), and a shorted branch means the wye center
+/// is electrically the same point as that neighbor, so the other two
+/// branches become direct connections from it instead.
+fn apply_delta_wye(graph: &mut CircuitGraph, branches: [(NodeIndex, ImpedanceResult); 3]) -> Result<ComponentIndex, CircuitError> {
+    let degenerate: Vec<usize> = (0..3).filter(|&i| !branches[i].1.is_finite()).collect();
+
+    match degenerate.as_slice() {
+        [] => {
+            let z_a = as_finite(&branches[0].1)?;
+            let z_b = as_finite(&branches[1].1)?;
+            let z_c = as_finite(&branches[2].1)?;
+            let (node_a, node_b, node_c) = (branches[0].0, branches[1].0, branches[2].0);
+            let sum_of_products = z_a * z_b + z_b * z_c + z_c * z_a;
+
+            let kind_ab = impedance_to_kind(ImpedanceResult::new_finite(sum_of_products / z_c))?;
+            let kind_bc = impedance_to_kind(ImpedanceResult::new_finite(sum_of_products / z_a))?;
+            let kind_ca = impedance_to_kind(ImpedanceResult::new_finite(sum_of_products / z_b))?;
+
+            graph.add_component("EQ_ab".to_string(), kind_ab, (node_a, node_b));
+            graph.add_component("EQ_bc".to_string(), kind_bc, (node_b, node_c));
+            Ok(graph.add_component("EQ_ca".to_string(), kind_ca, (node_c, node_a)))
+        }
+        [i] => {
+            let (x_node, zx) = branches[*i].clone();
+            let others: Vec<(NodeIndex, ImpedanceResult)> = (0..3).filter(|j| j != i).map(|j| branches[j].clone()).collect();
+            let (y_node, zy) = &others[0];
+            let (z_node, zz) = &others[1];
+            let zy = as_finite(zy)?;
+            let zz = as_finite(zz)?;
+
+            match zx {
+                ImpedanceResult::Open => {
+                    let kind = impedance_to_kind(ImpedanceResult::new_finite(zy + zz))?;
+                    Ok(graph.add_component("EQ".to_string(), kind, (*y_node, *z_node)))
+                }
+                ImpedanceResult::Short => {
+                    graph.add_component("EQ".to_string(), impedance_to_kind(ImpedanceResult::new_finite(zy))?, (x_node, *y_node));
+                    Ok(graph.add_component("EQ".to_string(), impedance_to_kind(ImpedanceResult::new_finite(zz))?, (x_node, *z_node)))
+                }
+                ImpedanceResult::Finite(_) => unreachable!("filtered out of `degenerate` above"),
+            }
+        }
+        _ => Err(CircuitError::InvalidImpedance(Complex64::new(0.0, 0.0))),
+    }
+}
+
+/// Unwraps a cached impedance known to be finite, as guaranteed by the
+/// `degenerate` filtering in [`apply_delta_wye`] and the `DeltaToWye`
+/// arm of [`apply_reduction`] above.
+fn as_finite(z: &ImpedanceResult) -> Result<Complex64, CircuitError> {
+    match z {
+        ImpedanceResult::Finite(v) => Ok(*v),
+        _ => Err(CircuitError::InvalidImpedance(Complex64::new(0.0, 0.0))),
     }
 }
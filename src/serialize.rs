@@ -0,0 +1,415 @@
+//! Persisting a [`CircuitGraph`] and a recorded [`Vec<ReductionStep>`].
+//!
+//! The binary format is deliberately simple and self-describing: a
+//! 4-byte magic number and a version byte (see [`GRAPH_MAGIC`] /
+//! [`GRAPH_FORMAT_VERSION`]), then a length-prefixed node list, then a
+//! length-prefixed component list where each component is tagged by a
+//! one-byte kind discriminant followed by its `Value`/complex fields and
+//! node indices. All integers are little-endian. `write_reduction_steps`
+//! skips its own magic/version pair -- a reduction trace is only ever
+//! meaningful alongside the graph it was recorded from (see
+//! [`write_solved`]), not as a standalone file another tool might sniff.
+//!
+//! `Node`, `CircuitComponent`, `CircuitGraph`, `ComponentKind`, the unit
+//! wrapper types and `ReductionStep` also carry `#[cfg_attr(feature =
+//! "serde", derive(...))]` so a human-readable JSON variant is available
+//! to callers who enable the `serde` feature instead of using this
+//! module's explicit codec.
+
+use std::io::{self, Read, Write};
+
+use num_complex::Complex64;
+
+/// Magic number stamped at the start of every [`CircuitGraph::write`]
+/// output, so [`CircuitGraph::read`] can reject data that isn't one of
+/// ours before it gets far enough to misinterpret random bytes as a huge
+/// node or component count.
+pub const GRAPH_MAGIC: [u8; 4] = *b"ECGR";
+
+/// Format version following [`GRAPH_MAGIC`]. Bump this and branch on it
+/// in [`CircuitGraph::read`] if the field layout ever changes.
+pub const GRAPH_FORMAT_VERSION: u8 = 1;
+
+use crate::component::ComponentKind;
+use crate::errors::CircuitError;
+use crate::graph::CircuitGraph;
+use crate::reduce::{apply_reduction, ReductionStep};
+use crate::units::{AngularFrequency, ImpedanceResult, Resistance, Inductance, Capacitance, Voltage, Current, Value};
+
+fn write_u32<W: Write>(w: &mut W, v: u32) -> io::Result<()> {
+    w.write_all(&v.to_le_bytes())
+}
+
+fn read_u32<R: Read>(r: &mut R) -> io::Result<u32> {
+    let mut buf = [0u8; 4];
+    r.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn write_f64<W: Write>(w: &mut W, v: f64) -> io::Result<()> {
+    w.write_all(&v.to_le_bytes())
+}
+
+fn read_f64<R: Read>(r: &mut R) -> io::Result<f64> {
+    let mut buf = [0u8; 8];
+    r.read_exact(&mut buf)?;
+    Ok(f64::from_le_bytes(buf))
+}
+
+fn write_complex<W: Write>(w: &mut W, z: Complex64) -> io::Result<()> {
+    write_f64(w, z.re)?;
+    write_f64(w, z.im)
+}
+
+fn read_complex<R: Read>(r: &mut R) -> io::Result<Complex64> {
+    Ok(Complex64::new(read_f64(r)?, read_f64(r)?))
+}
+
+fn write_string<W: Write>(w: &mut W, s: &str) -> io::Result<()> {
+    write_u32(w, s.len() as u32)?;
+    w.write_all(s.as_bytes())
+}
+
+fn read_string<R: Read>(r: &mut R) -> io::Result<String> {
+    let len = read_u32(r)? as usize;
+    let mut buf = vec![0u8; len];
+    r.read_exact(&mut buf)?;
+    String::from_utf8(buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn write_value<W: Write>(w: &mut W, value: &Value<f64>) -> io::Result<()> {
+    match value {
+        Value::Known(v) => {
+            w.write_all(&[0])?;
+            write_f64(w, *v)
+        }
+        Value::Unknown(name) => {
+            w.write_all(&[1])?;
+            write_string(w, name)
+        }
+    }
+}
+
+fn read_value<R: Read>(r: &mut R) -> io::Result<Value<f64>> {
+    let mut tag = [0u8; 1];
+    r.read_exact(&mut tag)?;
+    match tag[0] {
+        0 => Ok(Value::Known(read_f64(r)?)),
+        1 => Ok(Value::Unknown(read_string(r)?)),
+        other => Err(io::Error::new(io::ErrorKind::InvalidData, format!("unknown Value tag {other}"))),
+    }
+}
+
+fn write_impedance_result<W: Write>(w: &mut W, z: &ImpedanceResult) -> io::Result<()> {
+    match z {
+        ImpedanceResult::Finite(v) => {
+            w.write_all(&[0])?;
+            write_complex(w, *v)
+        }
+        ImpedanceResult::Open => w.write_all(&[1]),
+        ImpedanceResult::Short => w.write_all(&[2]),
+    }
+}
+
+fn read_impedance_result<R: Read>(r: &mut R) -> io::Result<ImpedanceResult> {
+    let mut tag = [0u8; 1];
+    r.read_exact(&mut tag)?;
+    match tag[0] {
+        0 => Ok(ImpedanceResult::Finite(read_complex(r)?)),
+        1 => Ok(ImpedanceResult::Open),
+        2 => Ok(ImpedanceResult::Short),
+        other => Err(io::Error::new(io::ErrorKind::InvalidData, format!("unknown ImpedanceResult tag {other}"))),
+    }
+}
+
+fn write_component_kind<W: Write>(w: &mut W, kind: &ComponentKind) -> io::Result<()> {
+    match kind {
+        ComponentKind::Resistor { r } => {
+            w.write_all(&[0])?;
+            write_value(w, &r.0)
+        }
+        ComponentKind::Inductor { l } => {
+            w.write_all(&[1])?;
+            write_value(w, &l.0)
+        }
+        ComponentKind::Capacitor { c } => {
+            w.write_all(&[2])?;
+            write_value(w, &c.0)
+        }
+        ComponentKind::VoltageSource { v } => {
+            w.write_all(&[3])?;
+            write_complex(w, v.0)
+        }
+        ComponentKind::CurrentSource { i } => {
+            w.write_all(&[4])?;
+            write_complex(w, i.0)
+        }
+        ComponentKind::Impedance { z } => {
+            w.write_all(&[5])?;
+            write_impedance_result(w, z)
+        }
+        ComponentKind::VCVS { gain, control_nodes } => {
+            w.write_all(&[6])?;
+            write_f64(w, *gain)?;
+            write_u32(w, control_nodes.0 as u32)?;
+            write_u32(w, control_nodes.1 as u32)
+        }
+        ComponentKind::VCCS { gain, control_nodes } => {
+            w.write_all(&[7])?;
+            write_f64(w, *gain)?;
+            write_u32(w, control_nodes.0 as u32)?;
+            write_u32(w, control_nodes.1 as u32)
+        }
+        ComponentKind::CCVS { gain, control_component } => {
+            w.write_all(&[8])?;
+            write_f64(w, *gain)?;
+            write_u32(w, *control_component as u32)
+        }
+        ComponentKind::CCCS { gain, control_component } => {
+            w.write_all(&[9])?;
+            write_f64(w, *gain)?;
+            write_u32(w, *control_component as u32)
+        }
+    }
+}
+
+fn read_component_kind<R: Read>(r: &mut R) -> io::Result<ComponentKind> {
+    let mut tag = [0u8; 1];
+    r.read_exact(&mut tag)?;
+    Ok(match tag[0] {
+        0 => ComponentKind::Resistor { r: Resistance(read_value(r)?) },
+        1 => ComponentKind::Inductor { l: Inductance(read_value(r)?) },
+        2 => ComponentKind::Capacitor { c: Capacitance(read_value(r)?) },
+        3 => ComponentKind::VoltageSource { v: Voltage(read_complex(r)?) },
+        4 => ComponentKind::CurrentSource { i: Current(read_complex(r)?) },
+        5 => ComponentKind::Impedance { z: read_impedance_result(r)? },
+        6 => ComponentKind::VCVS {
+            gain: read_f64(r)?,
+            control_nodes: (read_u32(r)? as usize, read_u32(r)? as usize),
+        },
+        7 => ComponentKind::VCCS {
+            gain: read_f64(r)?,
+            control_nodes: (read_u32(r)? as usize, read_u32(r)? as usize),
+        },
+        8 => ComponentKind::CCVS { gain: read_f64(r)?, control_component: read_u32(r)? as usize },
+        9 => ComponentKind::CCCS { gain: read_f64(r)?, control_component: read_u32(r)? as usize },
+        other => return Err(io::Error::new(io::ErrorKind::InvalidData, format!("unknown ComponentKind tag {other}"))),
+    })
+}
+
+impl CircuitGraph {
+    /// Writes this graph's nodes, components and ground node in the
+    /// binary format documented at the top of [`crate::serialize`]. The
+    /// per-component `cached_impedance` is a derived value and is not
+    /// written; a freshly [`read`](Self::read) graph always starts with
+    /// no cached impedances, same as a freshly parsed one.
+    pub fn write<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        w.write_all(&GRAPH_MAGIC)?;
+        w.write_all(&[GRAPH_FORMAT_VERSION])?;
+
+        write_u32(w, self.nodes.len() as u32)?;
+        for node in &self.nodes {
+            write_string(w, &node.id)?;
+        }
+
+        write_u32(w, self.components.len() as u32)?;
+        for comp in &self.components {
+            write_string(w, &comp.id)?;
+            write_component_kind(w, &comp.kind)?;
+            write_u32(w, comp.nodes.0 as u32)?;
+            write_u32(w, comp.nodes.1 as u32)?;
+            w.write_all(&[comp.is_active as u8])?;
+        }
+
+        match self.ground {
+            Some(idx) => {
+                w.write_all(&[1])?;
+                write_u32(w, idx as u32)
+            }
+            None => w.write_all(&[0]),
+        }
+    }
+
+    /// The inverse of [`Self::write`]. Rejects data that doesn't start
+    /// with [`GRAPH_MAGIC`] or whose version byte isn't one this build
+    /// knows how to read.
+    pub fn read<R: Read>(r: &mut R) -> io::Result<Self> {
+        let mut magic = [0u8; 4];
+        r.read_exact(&mut magic)?;
+        if magic != GRAPH_MAGIC {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("not an ElectroSolve circuit graph (bad magic {magic:?})"),
+            ));
+        }
+        let mut version = [0u8; 1];
+        r.read_exact(&mut version)?;
+        if version[0] != GRAPH_FORMAT_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unsupported circuit graph format version {}", version[0]),
+            ));
+        }
+
+        let mut graph = CircuitGraph::new();
+
+        let node_count = read_u32(r)?;
+        for _ in 0..node_count {
+            graph.add_node(read_string(r)?);
+        }
+
+        let component_count = read_u32(r)?;
+        for _ in 0..component_count {
+            let id = read_string(r)?;
+            let kind = read_component_kind(r)?;
+            let node0 = read_u32(r)? as usize;
+            let node1 = read_u32(r)? as usize;
+            let mut active_byte = [0u8; 1];
+            r.read_exact(&mut active_byte)?;
+
+            let idx = graph.add_component(id, kind, (node0, node1));
+            graph.components[idx].is_active = active_byte[0] != 0;
+        }
+
+        let mut ground_flag = [0u8; 1];
+        r.read_exact(&mut ground_flag)?;
+        if ground_flag[0] == 1 {
+            graph.set_ground(read_u32(r)? as usize);
+        }
+
+        Ok(graph)
+    }
+}
+
+/// Writes a recorded reduction trace so it can be shipped alongside (or
+/// instead of) the fully reduced graph and replayed later with [`replay`].
+pub fn write_reduction_steps<W: Write>(steps: &[ReductionStep], w: &mut W) -> io::Result<()> {
+    write_u32(w, steps.len() as u32)?;
+    for step in steps {
+        match step {
+            ReductionStep::Series { components, equivalent, impedance } => {
+                w.write_all(&[0])?;
+                write_u32(w, components.len() as u32)?;
+                for &c in components {
+                    write_u32(w, c as u32)?;
+                }
+                write_u32(w, *equivalent as u32)?;
+                write_complex(w, *impedance)?;
+            }
+            ReductionStep::Parallel { components, equivalent, impedance } => {
+                w.write_all(&[1])?;
+                write_u32(w, components.len() as u32)?;
+                for &c in components {
+                    write_u32(w, c as u32)?;
+                }
+                write_u32(w, *equivalent as u32)?;
+                write_complex(w, *impedance)?;
+            }
+            ReductionStep::DeltaWye { delta_nodes, wye_node } => {
+                w.write_all(&[2])?;
+                write_u32(w, delta_nodes.0 as u32)?;
+                write_u32(w, delta_nodes.1 as u32)?;
+                write_u32(w, delta_nodes.2 as u32)?;
+                write_u32(w, *wye_node as u32)?;
+            }
+            ReductionStep::DeltaToWye { triangle_nodes, triangle_components, new_node } => {
+                w.write_all(&[3])?;
+                write_u32(w, triangle_nodes.0 as u32)?;
+                write_u32(w, triangle_nodes.1 as u32)?;
+                write_u32(w, triangle_nodes.2 as u32)?;
+                write_u32(w, triangle_components.0 as u32)?;
+                write_u32(w, triangle_components.1 as u32)?;
+                write_u32(w, triangle_components.2 as u32)?;
+                write_u32(w, *new_node as u32)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The inverse of [`write_reduction_steps`].
+pub fn read_reduction_steps<R: Read>(r: &mut R) -> io::Result<Vec<ReductionStep>> {
+    let count = read_u32(r)?;
+    let mut steps = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let mut tag = [0u8; 1];
+        r.read_exact(&mut tag)?;
+        let step = match tag[0] {
+            0 | 1 => {
+                let len = read_u32(r)?;
+                let mut components = Vec::with_capacity(len as usize);
+                for _ in 0..len {
+                    components.push(read_u32(r)? as usize);
+                }
+                let equivalent = read_u32(r)? as usize;
+                let impedance = read_complex(r)?;
+                if tag[0] == 0 {
+                    ReductionStep::Series { components, equivalent, impedance }
+                } else {
+                    ReductionStep::Parallel { components, equivalent, impedance }
+                }
+            }
+            2 => {
+                let a = read_u32(r)? as usize;
+                let b = read_u32(r)? as usize;
+                let c = read_u32(r)? as usize;
+                let wye_node = read_u32(r)? as usize;
+                ReductionStep::DeltaWye { delta_nodes: (a, b, c), wye_node }
+            }
+            3 => {
+                let a = read_u32(r)? as usize;
+                let b = read_u32(r)? as usize;
+                let c = read_u32(r)? as usize;
+                let comp_ab = read_u32(r)? as usize;
+                let comp_bc = read_u32(r)? as usize;
+                let comp_ca = read_u32(r)? as usize;
+                let new_node = read_u32(r)? as usize;
+                ReductionStep::DeltaToWye {
+                    triangle_nodes: (a, b, c),
+                    triangle_components: (comp_ab, comp_bc, comp_ca),
+                    new_node,
+                }
+            }
+            other => {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, format!("unknown ReductionStep tag {other}")))
+            }
+        };
+        steps.push(step);
+    }
+    Ok(steps)
+}
+
+/// Bundles a graph and its recorded reduction trace into one
+/// self-contained blob: `graph.write` followed directly by
+/// `write_reduction_steps`. Lets callers persist or ship a solved circuit
+/// as a single artifact instead of juggling two separate byte streams.
+pub fn write_solved<W: Write>(graph: &CircuitGraph, steps: &[ReductionStep], w: &mut W) -> io::Result<()> {
+    graph.write(w)?;
+    write_reduction_steps(steps, w)
+}
+
+/// The inverse of [`write_solved`].
+pub fn read_solved<R: Read>(r: &mut R) -> io::Result<(CircuitGraph, Vec<ReductionStep>)> {
+    let graph = CircuitGraph::read(r)?;
+    let steps = read_reduction_steps(r)?;
+    Ok((graph, steps))
+}
+
+/// Re-applies a saved reduction trace to `graph`, mutating it into the
+/// same reduced shape the trace was originally recorded from. Intended
+/// for a freshly loaded (unreduced) graph: `reduce()` searches for moves
+/// by re-deriving them from the graph's current state, so replay uses
+/// `apply_reduction` directly and re-caches impedances exactly where
+/// `reduce()` does, instead of searching again.
+pub fn replay(graph: &mut CircuitGraph, steps: &[ReductionStep], omega: AngularFrequency) -> Result<(), CircuitError> {
+    graph.cache_impedances(omega);
+    for step in steps {
+        let mut step = step.clone();
+        let is_delta_wye = matches!(step, ReductionStep::DeltaWye { .. } | ReductionStep::DeltaToWye { .. });
+        apply_reduction(graph, &mut step)?;
+        if is_delta_wye {
+            graph.cache_impedances(omega);
+        }
+    }
+    Ok(())
+}
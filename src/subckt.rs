@@ -0,0 +1,237 @@
+//! Hierarchical subcircuits: `.subckt`/`.ends` definitions and `X`
+//! instantiations.
+//!
+//! [`CircuitGraph`](crate::graph::CircuitGraph) has no notion of
+//! hierarchy -- every component is wired directly between two of its
+//! flat node indices -- so subcircuits are resolved entirely as a text
+//! transform on logical lines, before [`crate::parser::parse_netlist`]
+//! ever lowers a line into the graph. [`flatten`] runs in two passes:
+//! first it pulls every `.subckt ... .ends` block out of the line stream
+//! into a definition table (keyed by name), leaving the remaining
+//! top-level lines behind; then it walks those top-level lines and
+//! recursively expands each `X` instance by substituting its body in
+//! place, with port node names mapped onto the instance's actual nodes,
+//! every other node name prefixed with the instance path (`X1/mid`), and
+//! every component id suffixed with `@` and that path (`R1@X1`), so that
+//! two instances of the same subcircuit never collide. Component ids are
+//! not prefixed.
+
+use crate::errors::ParseError;
+use crate::grammar::{self, Pair, Rule};
+use std::collections::HashMap;
+
+/// How many `X` instances deep a single expansion chain may go before
+/// it's treated as a runaway (most likely cyclic) reference.
+const MAX_SUBCKT_DEPTH: usize = 64;
+
+/// A logical line paired with the 1-based physical line number it
+/// started on, same shape as [`grammar::logical_lines`] returns.
+type Lines = Vec<(usize, String)>;
+
+/// A collected `.subckt NAME port...` / `.ends` block: its ordered port
+/// list and the raw logical lines making up its body.
+struct SubcktDef {
+    ports: Vec<String>,
+    body: Lines,
+}
+
+/// Expands every `.subckt`/`X` pair in `lines` away, returning a flat
+/// line stream [`crate::parser::parse_netlist`] can lower with no
+/// further hierarchy awareness. Lines with no subcircuit involvement
+/// (including `.end` and comments) pass through unchanged.
+pub(crate) fn flatten(lines: Lines) -> Result<Lines, ParseError> {
+    let (top_level, defs) = collect_subckts(lines)?;
+    expand_lines(&top_level, "", &HashMap::new(), &defs, &[])
+}
+
+/// Splits `lines` into the subcircuit-free top-level lines and a table
+/// of the subcircuit definitions found along the way.
+fn collect_subckts(lines: Lines) -> Result<(Lines, HashMap<String, SubcktDef>), ParseError> {
+    let mut top_level = Vec::new();
+    let mut defs: HashMap<String, SubcktDef> = HashMap::new();
+    let mut lines_iter = lines.into_iter();
+
+    while let Some((line_num, line)) = lines_iter.next() {
+        let pair = grammar::parse_line(&line)
+            .map_err(|e| ParseError { line: line_num, column: e.column, message: e.message })?;
+
+        let is_subckt_header = matches!(pair.rule, Rule::Directive)
+            && directive_keyword(&pair).eq_ignore_ascii_case("subckt");
+        if !is_subckt_header {
+            top_level.push((line_num, line));
+            continue;
+        }
+
+        let mut values = pair.children_of(Rule::Value).map(|v| v.text.to_string());
+        let name = values.next().ok_or_else(|| ParseError {
+            line: line_num,
+            column: pair.column,
+            message: ".subckt needs a name".to_string(),
+        })?;
+        let ports: Vec<String> = values.collect();
+
+        if defs.contains_key(&name) {
+            return Err(ParseError { line: line_num, column: pair.column, message: format!("subcircuit '{name}' is already defined") });
+        }
+
+        let mut body = Vec::new();
+        let mut terminated = false;
+        for (body_line_num, body_line) in lines_iter.by_ref() {
+            let body_pair = grammar::parse_line(&body_line)
+                .map_err(|e| ParseError { line: body_line_num, column: e.column, message: e.message })?;
+            if matches!(body_pair.rule, Rule::Directive) && directive_keyword(&body_pair).eq_ignore_ascii_case("ends") {
+                terminated = true;
+                break;
+            }
+            body.push((body_line_num, body_line));
+        }
+        if !terminated {
+            return Err(ParseError {
+                line: line_num,
+                column: pair.column,
+                message: format!("subcircuit '{name}' is missing a matching .ends"),
+            });
+        }
+
+        defs.insert(name, SubcktDef { ports, body });
+    }
+
+    Ok((top_level, defs))
+}
+
+fn directive_keyword<'a>(pair: &Pair<'a>) -> &'a str {
+    pair.children_of(Rule::Keyword).next().expect("a Directive pair always has a Keyword child").text
+}
+
+/// Recursively expands `lines` (either the top-level line stream, or a
+/// subcircuit's body during an `X` instance's expansion). `prefix` is
+/// the instance path (e.g. `"X1/"`, or `""` at the top level) new
+/// component ids and internal nodes are scoped under; `port_map` maps
+/// this body's port names onto the actual node names the enclosing
+/// instance was wired to.
+fn expand_lines(
+    lines: &[(usize, String)],
+    prefix: &str,
+    port_map: &HashMap<String, String>,
+    defs: &HashMap<String, SubcktDef>,
+    stack: &[String],
+) -> Result<Lines, ParseError> {
+    let mut out = Vec::new();
+    for (line_num, line) in lines {
+        let line_num = *line_num;
+        let pair = grammar::parse_line(line)
+            .map_err(|e| ParseError { line: line_num, column: e.column, message: e.message })?;
+
+        match pair.rule {
+            Rule::Comment | Rule::Directive => out.push((line_num, line.clone())),
+            Rule::Component => out.push((line_num, rewrite_component_line(&pair, prefix, port_map))),
+            Rule::Instance => {
+                let id = pair.children_of(Rule::ComponentId).next().expect("an Instance pair always has a ComponentId child").text;
+                let node_refs: Vec<&str> = pair.children_of(Rule::NodeRef).map(|p| p.text).collect();
+                let subckt_name =
+                    pair.children_of(Rule::Value).next().expect("an Instance pair always has a trailing Value child").text;
+
+                if stack.iter().any(|s| s == subckt_name) {
+                    return Err(ParseError {
+                        line: line_num,
+                        column: pair.column,
+                        message: format!("recursive subcircuit reference: '{subckt_name}'"),
+                    });
+                }
+                if stack.len() >= MAX_SUBCKT_DEPTH {
+                    return Err(ParseError {
+                        line: line_num,
+                        column: pair.column,
+                        message: format!("subcircuit nesting exceeds the maximum depth of {MAX_SUBCKT_DEPTH}"),
+                    });
+                }
+                let def = defs.get(subckt_name).ok_or_else(|| ParseError {
+                    line: line_num,
+                    column: pair.column,
+                    message: format!("unknown subcircuit: {subckt_name}"),
+                })?;
+                if node_refs.len() != def.ports.len() {
+                    return Err(ParseError {
+                        line: line_num,
+                        column: pair.column,
+                        message: format!(
+                            "{id}: subcircuit '{subckt_name}' expects {} node(s), got {}",
+                            def.ports.len(),
+                            node_refs.len()
+                        ),
+                    });
+                }
+
+                let inner_prefix = format!("{prefix}{id}/");
+                let inner_port_map: HashMap<String, String> = def
+                    .ports
+                    .iter()
+                    .cloned()
+                    .zip(node_refs.iter().map(|name| resolve_node(name, port_map, prefix)))
+                    .collect();
+                let mut inner_stack = stack.to_vec();
+                inner_stack.push(subckt_name.to_string());
+
+                out.extend(expand_lines(&def.body, &inner_prefix, &inner_port_map, defs, &inner_stack)?);
+            }
+            other => unreachable!("grammar::parse_line never returns a bare {other:?}"),
+        }
+    }
+    Ok(out)
+}
+
+/// Resolves a node name as written inside a subcircuit body into the
+/// name it should carry in the flattened netlist: ground is never
+/// scoped, a port name maps onto whatever node the instance was wired
+/// to, and anything else is an internal node, uniquified under `prefix`.
+fn resolve_node(name: &str, port_map: &HashMap<String, String>, prefix: &str) -> String {
+    if name == "0" || name.eq_ignore_ascii_case("gnd") {
+        return name.to_string();
+    }
+    if let Some(mapped) = port_map.get(name) {
+        return mapped.clone();
+    }
+    format!("{prefix}{name}")
+}
+
+/// Re-renders a `Component` line with its id scoped by [`scope_id`],
+/// every node name it references (including a VCVS/VCCS's controlling
+/// pair) resolved by [`resolve_node`], and a CCVS/CCCS's controlling
+/// source id scoped the same way as the id itself.
+fn rewrite_component_line(pair: &Pair<'_>, prefix: &str, port_map: &HashMap<String, String>) -> String {
+    let id = pair.children_of(Rule::ComponentId).next().expect("a Component pair always has a ComponentId child").text;
+    let first_char = id.chars().next().unwrap_or(' ');
+
+    let node_refs: Vec<&str> = pair.children_of(Rule::NodeRef).map(|p| p.text).collect();
+    let node1 = resolve_node(node_refs[0], port_map, prefix);
+    let node2 = resolve_node(node_refs[1], port_map, prefix);
+
+    let values: Vec<&str> = pair.children_of(Rule::Value).map(|v| v.text).collect();
+    let new_values: Vec<String> = match first_char {
+        // VCVS/VCCS: the first two values are the controlling node pair.
+        'E' | 'G' => values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| if i < 2 { resolve_node(v, port_map, prefix) } else { v.to_string() })
+            .collect(),
+        // CCVS/CCCS: the first value names the controlling source, scoped
+        // the same way as every other component id in this body.
+        'H' | 'F' => values.iter().enumerate().map(|(i, v)| if i == 0 { scope_id(v, prefix) } else { v.to_string() }).collect(),
+        _ => values.iter().map(|v| v.to_string()).collect(),
+    };
+
+    format!("{} {node1} {node2} {}", scope_id(id, prefix), new_values.join(" "))
+}
+
+/// Scopes a component id under `prefix` without disturbing its leading
+/// character -- [`crate::parser::lower_component`] dispatches on a
+/// component's type by the first character of its id, so a scoped id
+/// like `X1/R1` would (wrongly) re-dispatch as another subcircuit
+/// instance. Appending the scope after the id instead keeps the type
+/// character in place while still making the id globally unique.
+fn scope_id(id: &str, prefix: &str) -> String {
+    match prefix.trim_end_matches('/') {
+        "" => id.to_string(),
+        scope => format!("{id}@{scope}"),
+    }
+}
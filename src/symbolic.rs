@@ -0,0 +1,300 @@
+//! Symbolic equivalent impedance for circuits with [`Value::Unknown`]
+//! components.
+//!
+//! The numeric [`ImpedanceResult`] pipeline (`mna`, `reduce`) treats an
+//! unknown-valued component as an open circuit, since it has no number to
+//! stamp. This module instead represents the equivalent impedance of a
+//! network as a ratio of polynomials in the unknown symbols, so a circuit
+//! containing one or more `Unknown` parts can still be reduced -- and,
+//! for the common case of a single unknown, solved for the value that
+//! hits a target impedance.
+
+use std::collections::BTreeMap;
+
+use num_complex::Complex64;
+
+use crate::component::ComponentKind;
+use crate::errors::CircuitError;
+use crate::graph::{CircuitComponent, CircuitGraph, NodeIndex};
+use crate::units::{AngularFrequency, ImpedanceResult, Value};
+
+/// A monomial as a map from symbol name to exponent; an empty map is the
+/// constant monomial `1`.
+pub type Monomial = BTreeMap<String, u32>;
+
+/// A multivariate polynomial with `Complex64` coefficients, represented as
+/// a sparse sum of monomials. Zero-coefficient terms are never stored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Poly {
+    pub terms: BTreeMap<Monomial, Complex64>,
+}
+
+impl Poly {
+    pub fn zero() -> Self {
+        Self { terms: BTreeMap::new() }
+    }
+
+    pub fn constant(c: Complex64) -> Self {
+        let mut terms = BTreeMap::new();
+        if c.norm() > 0.0 {
+            terms.insert(Monomial::new(), c);
+        }
+        Self { terms }
+    }
+
+    pub fn symbol(name: &str) -> Self {
+        let mut mono = Monomial::new();
+        mono.insert(name.to_string(), 1);
+        let mut terms = BTreeMap::new();
+        terms.insert(mono, Complex64::new(1.0, 0.0));
+        Self { terms }
+    }
+
+    pub fn add(&self, other: &Poly) -> Poly {
+        let mut terms = self.terms.clone();
+        for (mono, coeff) in &other.terms {
+            *terms.entry(mono.clone()).or_insert(Complex64::new(0.0, 0.0)) += coeff;
+        }
+        terms.retain(|_, c| c.norm() > 1e-15);
+        Poly { terms }
+    }
+
+    pub fn scale(&self, factor: Complex64) -> Poly {
+        Poly { terms: self.terms.iter().map(|(m, c)| (m.clone(), c * factor)).collect() }
+    }
+
+    pub fn mul(&self, other: &Poly) -> Poly {
+        let mut terms: BTreeMap<Monomial, Complex64> = BTreeMap::new();
+        for (m1, c1) in &self.terms {
+            for (m2, c2) in &other.terms {
+                let mut mono = m1.clone();
+                for (sym, exp) in m2 {
+                    *mono.entry(sym.clone()).or_insert(0) += exp;
+                }
+                *terms.entry(mono).or_insert(Complex64::new(0.0, 0.0)) += c1 * c2;
+            }
+        }
+        terms.retain(|_, c| c.norm() > 1e-15);
+        Poly { terms }
+    }
+
+    /// Whether every monomial in this polynomial involves `symbol` alone
+    /// (or is the constant monomial) -- i.e. whether it's safe to read as a
+    /// plain univariate polynomial in `symbol`.
+    pub fn is_univariate_in(&self, symbol: &str) -> bool {
+        self.terms.keys().all(|m| m.is_empty() || (m.len() == 1 && m.contains_key(symbol)))
+    }
+
+    /// The highest power of `symbol` appearing, assuming [`Self::is_univariate_in`].
+    pub fn degree_in(&self, symbol: &str) -> u32 {
+        self.terms.keys().map(|m| m.get(symbol).copied().unwrap_or(0)).max().unwrap_or(0)
+    }
+
+    /// The coefficient of `symbol^power`, assuming [`Self::is_univariate_in`].
+    pub fn univariate_coeff(&self, symbol: &str, power: u32) -> Complex64 {
+        self.terms
+            .iter()
+            .filter(|(m, _)| m.get(symbol).copied().unwrap_or(0) == power)
+            .map(|(_, c)| *c)
+            .sum()
+    }
+}
+
+/// An equivalent impedance expressed symbolically, as `num / den` where
+/// both are [`Poly`] in the circuit's unknown component values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymImpedance {
+    pub num: Poly,
+    pub den: Poly,
+}
+
+impl SymImpedance {
+    pub fn known(z: Complex64) -> Self {
+        Self { num: Poly::constant(z), den: Poly::constant(Complex64::new(1.0, 0.0)) }
+    }
+
+    pub fn unknown(name: &str) -> Self {
+        Self { num: Poly::symbol(name), den: Poly::constant(Complex64::new(1.0, 0.0)) }
+    }
+}
+
+/// Series composition (`Z = Z1 + Z2`) lifted to polynomial ratios.
+pub fn sym_combine_series(a: &SymImpedance, b: &SymImpedance) -> SymImpedance {
+    SymImpedance {
+        num: a.num.mul(&b.den).add(&b.num.mul(&a.den)),
+        den: a.den.mul(&b.den),
+    }
+}
+
+/// Parallel composition (`1/Z = 1/Z1 + 1/Z2`) lifted to polynomial ratios.
+pub fn sym_combine_parallel(a: &SymImpedance, b: &SymImpedance) -> SymImpedance {
+    SymImpedance {
+        num: a.num.mul(&b.num),
+        den: a.num.mul(&b.den).add(&b.num.mul(&a.den)),
+    }
+}
+
+fn component_sym_impedance(kind: &ComponentKind, omega: AngularFrequency) -> Result<SymImpedance, CircuitError> {
+    let omega_val: f64 = f64::from(omega);
+    match kind {
+        ComponentKind::Resistor { r } => Ok(match &r.0 {
+            Value::Known(v) => SymImpedance::known(Complex64::new(*v, 0.0)),
+            Value::Unknown(name) => SymImpedance::unknown(name),
+        }),
+        ComponentKind::Inductor { l } => Ok(match &l.0 {
+            Value::Known(v) => SymImpedance::known(Complex64::new(0.0, omega_val * v)),
+            Value::Unknown(name) => SymImpedance {
+                num: Poly::symbol(name).scale(Complex64::new(0.0, omega_val)),
+                den: Poly::constant(Complex64::new(1.0, 0.0)),
+            },
+        }),
+        ComponentKind::Capacitor { c } => match &c.0 {
+            Value::Known(v) => {
+                if omega_val < 1e-12 {
+                    return Err(CircuitError::InvalidAngularFrequency(omega_val));
+                }
+                Ok(SymImpedance::known(Complex64::new(0.0, -1.0 / (omega_val * v))))
+            }
+            Value::Unknown(name) => Ok(SymImpedance {
+                num: Poly::constant(Complex64::new(1.0, 0.0)),
+                den: Poly::symbol(name).scale(Complex64::new(0.0, omega_val)),
+            }),
+        },
+        ComponentKind::Impedance { z } => match z {
+            ImpedanceResult::Finite(v) => Ok(SymImpedance::known(*v)),
+            ImpedanceResult::Short => Ok(SymImpedance::known(Complex64::new(0.0, 0.0))),
+            ImpedanceResult::Open => {
+                Err(CircuitError::SymbolicSolveFailed("open-circuit branch has no finite symbolic impedance".to_string()))
+            }
+        },
+        ComponentKind::VoltageSource { .. } | ComponentKind::CurrentSource { .. }
+        | ComponentKind::VCVS { .. } | ComponentKind::VCCS { .. }
+        | ComponentKind::CCVS { .. } | ComponentKind::CCCS { .. } => {
+            Err(CircuitError::SymbolicSolveFailed("sources have no symbolic equivalent impedance".to_string()))
+        }
+    }
+}
+
+fn same_pair(a: (NodeIndex, NodeIndex), b: (NodeIndex, NodeIndex)) -> bool {
+    a == b || a == (b.1, b.0)
+}
+
+/// A coarse structural check for "this is a simple series chain": every
+/// node touched by an active component has degree one (an endpoint) or two
+/// (a pass-through junction), so there's no branching for the symbolic
+/// walk to get wrong.
+fn is_series_chain(graph: &CircuitGraph, active: &[&CircuitComponent]) -> bool {
+    let mut touched: Vec<NodeIndex> = Vec::new();
+    for comp in active {
+        touched.push(comp.nodes.0);
+        touched.push(comp.nodes.1);
+    }
+    touched.sort_unstable();
+    touched.dedup();
+    touched.iter().all(|&n| matches!(graph.get_node_degree(n), 1 | 2))
+}
+
+/// Computes the symbolic equivalent impedance of a circuit's active
+/// components.
+///
+/// This only understands two topologies: every active component sharing
+/// the same pair of terminals (a parallel bank), or a single unbranched
+/// chain of components (a series run). Anything else -- bridges,
+/// multi-branch networks -- returns [`CircuitError::SymbolicSolveFailed`];
+/// handling arbitrary topologies symbolically would mean reimplementing
+/// `reduce`'s series/parallel search over polynomial ratios instead of
+/// `Complex64`, which is future work.
+pub fn graph_sym_impedance(graph: &CircuitGraph, omega: AngularFrequency) -> Result<SymImpedance, CircuitError> {
+    let active: Vec<&CircuitComponent> = graph.components.iter().filter(|c| c.is_active).collect();
+    if active.is_empty() {
+        return Err(CircuitError::SymbolicSolveFailed("no active components".to_string()));
+    }
+
+    let impedances: Vec<SymImpedance> =
+        active.iter().map(|c| component_sym_impedance(&c.kind, omega)).collect::<Result<_, _>>()?;
+
+    if active.len() == 1 {
+        return Ok(impedances.into_iter().next().unwrap());
+    }
+
+    let first_pair = active[0].nodes;
+    if active.iter().all(|c| same_pair(c.nodes, first_pair)) {
+        let mut acc = impedances[0].clone();
+        for z in &impedances[1..] {
+            acc = sym_combine_parallel(&acc, z);
+        }
+        return Ok(acc);
+    }
+
+    if is_series_chain(graph, &active) {
+        let mut acc = impedances[0].clone();
+        for z in &impedances[1..] {
+            acc = sym_combine_series(&acc, z);
+        }
+        return Ok(acc);
+    }
+
+    Err(CircuitError::SymbolicSolveFailed(
+        "topology is neither a single series chain nor a single parallel bank".to_string(),
+    ))
+}
+
+/// Solves for the real value of `unknown` that makes the circuit's
+/// equivalent impedance equal `target`, for the common case where the
+/// equivalent impedance is linear or quadratic in that symbol.
+///
+/// Returns every physically valid (non-negative, real) root; there may be
+/// zero, one, or two.
+pub fn solve_for(
+    graph: &CircuitGraph,
+    unknown: &str,
+    target: ImpedanceResult,
+    omega: AngularFrequency,
+) -> Result<Vec<f64>, CircuitError> {
+    let sym = graph_sym_impedance(graph, omega)?;
+    let target_z = match target {
+        ImpedanceResult::Finite(z) => z,
+        ImpedanceResult::Short => Complex64::new(0.0, 0.0),
+        ImpedanceResult::Open => {
+            return Err(CircuitError::SymbolicSolveFailed("cannot solve for an open-circuit target".to_string()))
+        }
+    };
+
+    if !sym.num.is_univariate_in(unknown) || !sym.den.is_univariate_in(unknown) {
+        return Err(CircuitError::SymbolicSolveFailed(format!(
+            "equivalent impedance is not a polynomial purely in '{unknown}'"
+        )));
+    }
+
+    // num(x) = target * den(x)  =>  num(x) - target * den(x) = 0
+    let equation = sym.num.add(&sym.den.scale(-target_z));
+    let degree = equation.degree_in(unknown);
+    let c0 = equation.univariate_coeff(unknown, 0);
+    let c1 = equation.univariate_coeff(unknown, 1);
+    let c2 = equation.univariate_coeff(unknown, 2);
+
+    let roots: Vec<Complex64> = match degree {
+        0 => {
+            return Err(CircuitError::SymbolicSolveFailed(format!(
+                "equivalent impedance does not depend on '{unknown}'"
+            )))
+        }
+        1 => vec![-c0 / c1],
+        2 => {
+            let disc = (c1 * c1 - Complex64::new(4.0, 0.0) * c2 * c0).sqrt();
+            let two = Complex64::new(2.0, 0.0);
+            vec![(-c1 + disc) / (two * c2), (-c1 - disc) / (two * c2)]
+        }
+        _ => {
+            return Err(CircuitError::SymbolicSolveFailed(format!(
+                "equivalent impedance is degree {degree} in '{unknown}', only linear/quadratic is supported"
+            )))
+        }
+    };
+
+    let mut real_roots: Vec<f64> =
+        roots.into_iter().filter(|r| r.im.abs() < 1e-6 && r.re >= 0.0).map(|r| r.re).collect();
+    real_roots.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    real_roots.dedup_by(|a, b| (*a - *b).abs() < 1e-9);
+    Ok(real_roots)
+}
@@ -2,6 +2,7 @@ use num_complex::Complex64;
 use crate::errors::CircuitError;
 
 #[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Value<T> {
     Known(T),
     Unknown(String),
@@ -34,6 +35,7 @@ impl<T> Value<T> {
 
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AngularFrequency(f64);
 
 impl AngularFrequency {
@@ -56,6 +58,7 @@ impl From<AngularFrequency> for f64 {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Resistance(pub Value<f64>);
 
 impl Resistance {
@@ -73,10 +76,25 @@ impl Resistance {
     pub fn is_known(&self) -> bool {
         self.0.is_known()
     }
-    
+
     pub fn is_unknown(&self) -> bool {
         self.0.is_unknown()
     }
+
+    /// The resistance of `self` and `other` wired in series: `R1 + R2`.
+    /// `None` if either value is unknown (symbolic).
+    pub fn in_series(&self, other: &Resistance) -> Option<Resistance> {
+        let (Value::Known(r1), Value::Known(r2)) = (&self.0, &other.0) else { return None };
+        Resistance::known(r1 + r2).ok()
+    }
+
+    /// The resistance of `self` and `other` wired in parallel:
+    /// `R1 * R2 / (R1 + R2)`. `None` if either value is unknown
+    /// (symbolic).
+    pub fn in_parallel(&self, other: &Resistance) -> Option<Resistance> {
+        let (Value::Known(r1), Value::Known(r2)) = (&self.0, &other.0) else { return None };
+        Resistance::known((r1 * r2) / (r1 + r2)).ok()
+    }
 }
 
 impl From<Resistance> for Option<f64> {
@@ -89,6 +107,7 @@ impl From<Resistance> for Option<f64> {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Inductance(pub Value<f64>);
 
 impl Inductance {
@@ -106,10 +125,25 @@ impl Inductance {
     pub fn is_known(&self) -> bool {
         self.0.is_known()
     }
-    
+
     pub fn is_unknown(&self) -> bool {
         self.0.is_unknown()
     }
+
+    /// The inductance of `self` and `other` wired in series: `L1 + L2`.
+    /// `None` if either value is unknown (symbolic).
+    pub fn in_series(&self, other: &Inductance) -> Option<Inductance> {
+        let (Value::Known(l1), Value::Known(l2)) = (&self.0, &other.0) else { return None };
+        Inductance::known(l1 + l2).ok()
+    }
+
+    /// The inductance of `self` and `other` wired in parallel:
+    /// `L1 * L2 / (L1 + L2)`. `None` if either value is unknown
+    /// (symbolic).
+    pub fn in_parallel(&self, other: &Inductance) -> Option<Inductance> {
+        let (Value::Known(l1), Value::Known(l2)) = (&self.0, &other.0) else { return None };
+        Inductance::known((l1 * l2) / (l1 + l2)).ok()
+    }
 }
 
 impl From<Inductance> for Option<f64> {
@@ -123,6 +157,7 @@ impl From<Inductance> for Option<f64> {
 
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Capacitance(pub Value<f64>);
 
 impl Capacitance {
@@ -140,10 +175,25 @@ impl Capacitance {
     pub fn is_known(&self) -> bool {
         self.0.is_known()
     }
-    
+
     pub fn is_unknown(&self) -> bool {
         self.0.is_unknown()
     }
+
+    /// The capacitance of `self` and `other` wired in series:
+    /// `C1 * C2 / (C1 + C2)`. `None` if either value is unknown
+    /// (symbolic).
+    pub fn in_series(&self, other: &Capacitance) -> Option<Capacitance> {
+        let (Value::Known(c1), Value::Known(c2)) = (&self.0, &other.0) else { return None };
+        Capacitance::known((c1 * c2) / (c1 + c2)).ok()
+    }
+
+    /// The capacitance of `self` and `other` wired in parallel:
+    /// `C1 + C2`. `None` if either value is unknown (symbolic).
+    pub fn in_parallel(&self, other: &Capacitance) -> Option<Capacitance> {
+        let (Value::Known(c1), Value::Known(c2)) = (&self.0, &other.0) else { return None };
+        Capacitance::known(c1 + c2).ok()
+    }
 }
 
 impl From<Capacitance> for Option<f64> {
@@ -156,6 +206,7 @@ impl From<Capacitance> for Option<f64> {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Voltage(pub Complex64);
 
 impl Voltage {
@@ -178,6 +229,7 @@ impl Into<Complex64> for Voltage {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Current(pub Complex64);
 
 impl Current {
@@ -200,12 +252,14 @@ impl Into<Complex64> for Current {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ImpedanceResult {
     Finite(Complex64),
     Open,
     Short,
 }
 impl ImpedanceResult {
+    pub fn new_finite(z: Complex64) -> Self { Self::Finite(z) }
     pub fn is_finite(&self) -> bool { matches!(self, Self::Finite(..)) }
     pub fn is_open(&self) -> bool { matches!(self, Self::Open) }
     pub fn is_short(&self) -> bool { matches!(self, Self::Short) }
@@ -0,0 +1,72 @@
+use electro_solve::analysis::{ac_sweep, impulse_response, magnitude_db, phase_degrees, Scale};
+use electro_solve::graph::CircuitGraph;
+use electro_solve::units::ImpedanceResult;
+
+mod common;
+use common::create_resistor;
+
+#[test]
+fn sweep_of_a_pure_resistor_is_flat() {
+    let mut graph = CircuitGraph::new();
+    graph.add_node("a".to_string());
+    graph.add_node("b".to_string());
+    graph.set_ground(1);
+    create_resistor(&mut graph, "R1", 50.0, 0, 1);
+
+    let points = ac_sweep(&graph, 0, 1, 20.0, 20_000.0, 8, Scale::Log).unwrap();
+    assert_eq!(points.len(), 8);
+    for (_, z) in &points {
+        match z {
+            ImpedanceResult::Finite(z) => {
+                assert!((z.re - 50.0).abs() < 1e-6);
+                assert!(z.im.abs() < 1e-6);
+                assert!((magnitude_db(*z) - 20.0 * 50f64.log10()).abs() < 1e-6);
+                assert!(phase_degrees(*z).abs() < 1e-6);
+            }
+            _ => panic!("a plain resistor should always report a finite impedance"),
+        }
+    }
+}
+
+#[test]
+fn log_sweep_endpoints_match_requested_range() {
+    let mut graph = CircuitGraph::new();
+    graph.add_node("a".to_string());
+    graph.add_node("b".to_string());
+    graph.set_ground(1);
+    create_resistor(&mut graph, "R1", 1.0, 0, 1);
+
+    let points = ac_sweep(&graph, 0, 1, 10.0, 10_000.0, 5, Scale::Log).unwrap();
+    assert!((points.first().unwrap().0 - 10.0).abs() < 1e-9);
+    assert!((points.last().unwrap().0 - 10_000.0).abs() < 1e-6);
+}
+
+#[test]
+fn impulse_response_of_a_resistor_is_a_scaled_delta() {
+    let mut graph = CircuitGraph::new();
+    graph.add_node("a".to_string());
+    graph.add_node("b".to_string());
+    graph.set_ground(1);
+    create_resistor(&mut graph, "R1", 8.0, 0, 1);
+
+    let (samples, dt) = impulse_response(&graph, 0, 1, 1000.0, 16).unwrap();
+    assert_eq!(samples.len(), 16);
+    assert!((dt - 1.0 / 2000.0).abs() < 1e-12);
+    // A frequency-independent impedance has a flat spectrum, so its
+    // inverse FFT is a single spike at t = 0.
+    assert!((samples[0] - 8.0).abs() < 1e-6);
+    for &s in &samples[1..] {
+        assert!(s.abs() < 1e-6, "expected near-zero tail, got {s}");
+    }
+}
+
+#[test]
+fn impulse_response_rejects_non_power_of_two() {
+    let mut graph = CircuitGraph::new();
+    graph.add_node("a".to_string());
+    graph.add_node("b".to_string());
+    graph.set_ground(1);
+    create_resistor(&mut graph, "R1", 8.0, 0, 1);
+
+    assert!(impulse_response(&graph, 0, 1, 1000.0, 10).is_err());
+}
@@ -5,6 +5,7 @@ use electro_solve::units::*;
 use electro_solve::graph::*;
 use electro_solve::component::*;
 use electro_solve::reduce::*;
+use electro_solve::mna::equivalent_impedance;
 
 mod common;
 use common::*;
@@ -185,4 +186,91 @@ fn prop_reduction_is_idempotent(
     assert_impedance_eq(z1, z2, EPSILON_STRICT);
 }
 
+#[test]
+fn prop_bridge_topology_reduces_via_delta_wye(
+    r1 in 1.0_f64..1e5_f64,
+    r2 in 1.0_f64..1e5_f64,
+    r3 in 1.0_f64..1e5_f64,
+    r4 in 1.0_f64..1e5_f64,
+    rg in 1.0_f64..1e5_f64,
+) {
+    // Classic Wheatstone bridge: a-R1->top-R3->b, a-R2->bottom-R4->b, with a
+    // galvanometer Rg bridging top and bottom. No two resistors here are
+    // purely in series or purely in parallel, so series/parallel reduction
+    // alone stalls -- a delta-wye transform is required to make progress.
+    let mut graph = CircuitGraph::new();
+    graph.add_node("a".to_string());
+    graph.add_node("b".to_string());
+    graph.add_node("top".to_string());
+    graph.add_node("bottom".to_string());
+    graph.set_ground(1);
+
+    create_resistor(&mut graph, "R1", r1, 0, 2);
+    create_resistor(&mut graph, "R2", r2, 0, 3);
+    create_resistor(&mut graph, "R3", r3, 2, 1);
+    create_resistor(&mut graph, "R4", r4, 3, 1);
+    create_resistor(&mut graph, "Rg", rg, 2, 3);
+
+    let omega = AngularFrequency::new(1.0).unwrap();
+    let steps = reduce(&mut graph, omega).expect("bridge circuits should reduce without error");
+
+    prop_assert!(steps.iter().any(|s| matches!(s, ReductionStep::DeltaWye { .. })),
+        "a bridge network should require at least one delta-wye transform");
+    prop_assert!(graph.active_component_count() < 5,
+        "reduction should make progress on a bridge network");
+
+    let final_z = calculate_equivalent_impedance(&graph, omega);
+    prop_assert!(is_passive_impedance_result(&final_z));
+}
+
+}
+
+/// A triangle a-b-c where every corner also carries two pendant branches
+/// out to the network's terminals. No corner has degree 3, so neither
+/// series reduction (needs a degree-2 node) nor `find_delta_wye_reduction`
+/// (needs an exact degree-3 wye) can make progress -- only the
+/// triangle-to-star direction can unblock this network, so this exercises
+/// `find_delta_to_wye_reduction` specifically rather than its dual.
+///
+/// `calculate_equivalent_impedance` only reports the first active
+/// component's impedance, so it can't validate a network that doesn't
+/// collapse to a single component; `mna::equivalent_impedance` solves the
+/// full admittance system instead and works regardless of reduction state.
+#[test]
+fn delta_to_wye_reduction_preserves_equivalent_impedance_between_pendant_terminals() {
+    let mut graph = CircuitGraph::new();
+    let a = graph.add_node("a".to_string());
+    let b = graph.add_node("b".to_string());
+    let c = graph.add_node("c".to_string());
+    let pa1 = graph.add_node("pa1".to_string());
+    let pa2 = graph.add_node("pa2".to_string());
+    let pb1 = graph.add_node("pb1".to_string());
+    let pb2 = graph.add_node("pb2".to_string());
+    let pc1 = graph.add_node("pc1".to_string());
+    let pc2 = graph.add_node("pc2".to_string());
+    graph.set_ground(pc2);
+
+    create_resistor(&mut graph, "Rab", 100.0, a, b);
+    create_resistor(&mut graph, "Rbc", 150.0, b, c);
+    create_resistor(&mut graph, "Rca", 200.0, c, a);
+    create_resistor(&mut graph, "Ra1", 50.0, a, pa1);
+    create_resistor(&mut graph, "Ra2", 60.0, a, pa2);
+    create_resistor(&mut graph, "Rb1", 70.0, b, pb1);
+    create_resistor(&mut graph, "Rb2", 80.0, b, pb2);
+    create_resistor(&mut graph, "Rc1", 90.0, c, pc1);
+    create_resistor(&mut graph, "Rc2", 110.0, c, pc2);
+
+    let omega = AngularFrequency::new(1.0).unwrap();
+    let expected = equivalent_impedance(&graph, omega, pa1, pb1);
+
+    let mut reduced = graph.clone();
+    let steps = reduce(&mut reduced, omega).expect("should reduce without error");
+
+    assert!(
+        steps.iter().any(|s| matches!(s, ReductionStep::DeltaToWye { .. })),
+        "a triangle with no degree-2 or degree-3 corners should require a delta-to-wye transform"
+    );
+
+    let actual = equivalent_impedance(&reduced, omega, pa1, pb1);
+    assert_impedance_eq(actual, expected, EPSILON_PHYSICAL);
 }
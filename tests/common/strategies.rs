@@ -23,6 +23,8 @@ pub fn arbitrary_component_kind() -> impl Strategy<Value = ComponentKind> {
         (1e-12_f64..1e12_f64).prop_map(|c| ComponentKind::Capacitor{c: Capacitance::known(c).unwrap()}),
         (1e-12_f64..1e12_f64).prop_map(|v| ComponentKind::VoltageSource{v: Voltage::dc(v)}),
         (1e-12_f64..1e12_f64).prop_map(|i| ComponentKind::CurrentSource{i: Current::dc(i)}),
+        (1e-12_f64..1e12_f64, 0.0_f64..360.0_f64).prop_map(|(mag, phase)| ComponentKind::VoltageSource{v: Voltage::ac_phasor(mag, phase)}),
+        (1e-12_f64..1e12_f64, 0.0_f64..360.0_f64).prop_map(|(mag, phase)| ComponentKind::CurrentSource{i: Current::ac_phasor(mag, phase)}),
     ]
 }
 
@@ -0,0 +1,175 @@
+use num_complex::Complex64;
+
+use electro_solve::component::ComponentKind;
+use electro_solve::graph::CircuitGraph;
+use electro_solve::mna::equivalent_impedance;
+use electro_solve::netlist::parse;
+use electro_solve::units::*;
+
+mod common;
+use common::*;
+
+/// An ideal VCVS with gain `g` pins its output node at `g * v_control`
+/// regardless of the load drawn from it, so probing it with
+/// `equivalent_impedance`'s 1 A test current should read back that fixed
+/// voltage directly.
+#[test]
+fn vcvs_pins_output_voltage_to_gain_times_control_voltage() {
+    let mut graph = CircuitGraph::new();
+    let gnd = graph.add_node("gnd".to_string());
+    let ctrl = graph.add_node("ctrl".to_string());
+    let out = graph.add_node("out".to_string());
+    graph.set_ground(gnd);
+
+    graph.add_component(
+        "V1".to_string(),
+        ComponentKind::VoltageSource { v: Voltage::dc(1.0) },
+        (ctrl, gnd),
+    );
+    graph.add_component(
+        "E1".to_string(),
+        ComponentKind::VCVS { gain: 5.0, control_nodes: (ctrl, gnd) },
+        (out, gnd),
+    );
+
+    let omega = AngularFrequency::new(1.0).unwrap();
+    let z = equivalent_impedance(&graph, omega, out, gnd);
+    assert_impedance_eq(z, ImpedanceResult::new_finite(Complex64::new(5.0, 0.0)), EPSILON_PHYSICAL);
+}
+
+/// Self-coupling a VCCS's control pair to its own output pair turns it
+/// into a plain dependent admittance `gain` in parallel with whatever else
+/// is on that node -- a case with no other independent source to
+/// superpose with, so the impedance looking into the node is exactly
+/// `1 / (1/r_load + gain)`.
+#[test]
+fn vccs_self_coupled_to_its_output_acts_as_a_parallel_admittance() {
+    let mut graph = CircuitGraph::new();
+    let gnd = graph.add_node("gnd".to_string());
+    let out = graph.add_node("out".to_string());
+    graph.set_ground(gnd);
+
+    create_resistor(&mut graph, "Rload", 100.0, out, gnd);
+    graph.add_component(
+        "G1".to_string(),
+        ComponentKind::VCCS { gain: 0.004, control_nodes: (out, gnd) },
+        (out, gnd),
+    );
+
+    let omega = AngularFrequency::new(1.0).unwrap();
+    let z = equivalent_impedance(&graph, omega, out, gnd);
+
+    let expected = 1.0 / (1.0 / 100.0 + 0.004);
+    assert_impedance_eq(z, ImpedanceResult::new_finite(Complex64::new(expected, 0.0)), EPSILON_PHYSICAL);
+}
+
+/// A CCVS pins its output voltage to `gain * i_control`, where `i_control`
+/// is the current flowing through the controlling voltage source (here, a
+/// 1 V source across a 1 ohm sense resistor draws exactly 1 A).
+#[test]
+fn ccvs_pins_output_voltage_to_gain_times_control_current() {
+    let mut graph = CircuitGraph::new();
+    let gnd = graph.add_node("gnd".to_string());
+    let sense = graph.add_node("sense".to_string());
+    let out = graph.add_node("out".to_string());
+    graph.set_ground(gnd);
+
+    graph.add_component(
+        "Vsense".to_string(),
+        ComponentKind::VoltageSource { v: Voltage::dc(1.0) },
+        (sense, gnd),
+    );
+    create_resistor(&mut graph, "Rsense", 1.0, sense, gnd);
+
+    let vsense_idx = graph.components.iter().position(|c| c.id == "Vsense").unwrap();
+    graph.add_component(
+        "H1".to_string(),
+        ComponentKind::CCVS { gain: 10.0, control_component: vsense_idx },
+        (out, gnd),
+    );
+
+    let omega = AngularFrequency::new(1.0).unwrap();
+    let z = equivalent_impedance(&graph, omega, out, gnd);
+    // |i_control| = 1 A, gain = 10 -> |v_out| = 10 V (see the MNA branch
+    // current sign convention documented in `mna.rs`).
+    assert_impedance_eq(z, ImpedanceResult::new_finite(Complex64::new(-10.0, 0.0)), EPSILON_PHYSICAL);
+}
+
+/// A CCCS drives `gain * i_control` out of its output node; sensed through
+/// a 1 ohm resistor across a 1 V source, `i_control` is 1 A in magnitude.
+#[test]
+fn cccs_drives_output_current_proportional_to_control_current() {
+    let mut graph = CircuitGraph::new();
+    let gnd = graph.add_node("gnd".to_string());
+    let sense = graph.add_node("sense".to_string());
+    let out = graph.add_node("out".to_string());
+    graph.set_ground(gnd);
+
+    graph.add_component(
+        "Vsense".to_string(),
+        ComponentKind::VoltageSource { v: Voltage::dc(1.0) },
+        (sense, gnd),
+    );
+    create_resistor(&mut graph, "Rsense", 1.0, sense, gnd);
+
+    let vsense_idx = graph.components.iter().position(|c| c.id == "Vsense").unwrap();
+    graph.add_component(
+        "F1".to_string(),
+        ComponentKind::CCCS { gain: 3.0, control_component: vsense_idx },
+        (out, gnd),
+    );
+    create_resistor(&mut graph, "Rload", 50.0, out, gnd);
+
+    let omega = AngularFrequency::new(1.0).unwrap();
+    let z = equivalent_impedance(&graph, omega, out, gnd);
+    // `equivalent_impedance` solves with Vsense active and a 1 A test
+    // current superposed, so this is Vsense's contribution (150 V, via
+    // the -1 A branch current this sign convention gives Rsense) plus the
+    // test current's own 50 V across the same load resistor.
+    assert_impedance_eq(z, ImpedanceResult::new_finite(Complex64::new(200.0, 0.0)), EPSILON_PHYSICAL);
+}
+
+/// The SPICE `E`/`G`/`H`/`F` netlist forms all parse into the right
+/// `ComponentKind`, and `to_netlist` renders them back out in a form that
+/// reparses to the same component.
+#[test]
+fn controlled_source_netlist_lines_round_trip() {
+    let text = "\
+Vin in 0 1
+E1 out 0 in 0 5
+R1 out 0 1k
+.end";
+    let graph = parse(text).unwrap();
+    let e1 = graph.components.iter().find(|c| c.id == "E1").unwrap();
+    match &e1.kind {
+        ComponentKind::VCVS { gain, .. } => assert_eq!(*gain, 5.0),
+        other => panic!("expected a VCVS, got {other:?}"),
+    }
+
+    let rendered = electro_solve::netlist::to_netlist(&graph);
+    let reparsed = parse(&rendered).unwrap();
+    let e1_again = reparsed.components.iter().find(|c| c.id == "E1").unwrap();
+    assert!(matches!(e1_again.kind, ComponentKind::VCVS { gain, .. } if gain == 5.0));
+}
+
+/// `H`/`F` lines name a controlling source by id; an id that hasn't been
+/// defined (yet, or at all) is a parse error rather than a panic.
+#[test]
+fn an_undefined_controlling_source_is_a_parse_error() {
+    let text = "H1 out 0 Vghost 10\n.end";
+    assert!(parse(text).is_err());
+}
+
+/// A controlling source id that does resolve to an existing component, but
+/// one with no branch-current unknown of its own (e.g. a plain resistor),
+/// is also a parse error -- it can't be sensed by `H`/`F`, and letting it
+/// through would panic deep inside `mna::equivalent_impedance` instead.
+#[test]
+fn a_controlling_source_that_is_not_a_branch_current_kind_is_a_parse_error() {
+    let text = "\
+Vin in 0 1
+R1 in 0 1k
+H1 out 0 R1 10
+.end";
+    assert!(parse(text).is_err());
+}
@@ -0,0 +1,94 @@
+use electro_solve::units::*;
+use electro_solve::component::*;
+use electro_solve::graph::*;
+
+/// The DOT output opens with a `graph` header and declares one vertex per
+/// node and one edge per component.
+#[test]
+fn to_dot_declares_a_vertex_per_node_and_an_edge_per_component() {
+    let mut graph = CircuitGraph::new();
+    let a = graph.add_node("a".to_string());
+    let b = graph.add_node("b".to_string());
+    graph.set_ground(a);
+    graph.add_component("R1".to_string(), ComponentKind::Resistor { r: Resistance::known(100.0).unwrap() }, (a, b));
+
+    let dot = graph.to_dot();
+
+    assert!(dot.starts_with("graph Circuit {"));
+    assert!(dot.contains("N0"));
+    assert!(dot.contains("N1"));
+    assert!(dot.contains("N0 -- N1"));
+    assert!(dot.contains("R1 100Ω"));
+}
+
+/// The ground node is drawn specially (filled, double-circle) so it's
+/// visually distinguishable from every other node.
+#[test]
+fn the_ground_node_is_styled_differently() {
+    let mut graph = CircuitGraph::new();
+    let gnd = graph.add_node("0".to_string());
+    let a = graph.add_node("a".to_string());
+    graph.set_ground(gnd);
+    graph.add_component("R1".to_string(), ComponentKind::Resistor { r: Resistance::known(50.0).unwrap() }, (gnd, a));
+
+    let dot = graph.to_dot();
+
+    let ground_line = dot.lines().find(|l| l.contains("N0 [")).unwrap();
+    assert!(ground_line.contains("doublecircle"));
+    let other_line = dot.lines().find(|l| l.contains("N1 [")).unwrap();
+    assert!(!other_line.contains("doublecircle"));
+}
+
+/// A component deactivated by a reduction pass still appears in the
+/// output, but rendered as a dashed edge rather than a solid one.
+#[test]
+fn inactive_components_are_rendered_as_dashed_edges() {
+    let mut graph = CircuitGraph::new();
+    let a = graph.add_node("a".to_string());
+    let b = graph.add_node("b".to_string());
+    let c = graph.add_node("c".to_string());
+    graph.add_component("R1".to_string(), ComponentKind::Resistor { r: Resistance::known(10.0).unwrap() }, (a, b));
+    graph.add_component("R2".to_string(), ComponentKind::Resistor { r: Resistance::known(20.0).unwrap() }, (b, c));
+
+    graph.reduce_series_parallel();
+
+    let dot = graph.to_dot();
+    let r1_line = dot.lines().find(|l| l.contains("\"R1")).unwrap();
+    assert!(r1_line.contains("style=dashed"));
+    let eq_line = dot.lines().find(|l| l.contains("\"EQ")).unwrap();
+    assert!(eq_line.contains("style=solid"));
+}
+
+/// Each of the passive kinds carries its own unit suffix in the edge
+/// label.
+#[test]
+fn each_passive_kind_carries_its_unit_suffix() {
+    let mut graph = CircuitGraph::new();
+    let a = graph.add_node("a".to_string());
+    let b = graph.add_node("b".to_string());
+    let c = graph.add_node("c".to_string());
+    let d = graph.add_node("d".to_string());
+    graph.add_component("L1".to_string(), ComponentKind::Inductor { l: Inductance::known(1e-3).unwrap() }, (a, b));
+    graph.add_component("C1".to_string(), ComponentKind::Capacitor { c: Capacitance::known(1e-6).unwrap() }, (b, c));
+    graph.add_component("V1".to_string(), ComponentKind::VoltageSource { v: Voltage::dc(5.0) }, (c, d));
+
+    let dot = graph.to_dot();
+
+    assert!(dot.contains("L1 0.001H"));
+    assert!(dot.contains("C1 0.000001F"));
+    assert!(dot.contains("V1 5+0iV"));
+}
+
+/// A symbolic (unknown) resistance is labeled with its name rather than
+/// a numeric value.
+#[test]
+fn a_symbolic_value_is_labeled_with_its_name() {
+    let mut graph = CircuitGraph::new();
+    let a = graph.add_node("a".to_string());
+    let b = graph.add_node("b".to_string());
+    graph.add_component("R1".to_string(), ComponentKind::Resistor { r: Resistance::unknown("Rx".to_string()) }, (a, b));
+
+    let dot = graph.to_dot();
+
+    assert!(dot.contains("R1 RxΩ"));
+}
@@ -217,11 +217,143 @@ fn prop_ground_persists_after_operations(
 ) {
     if graph.nodes.len() > gnd_idx {
         graph.set_ground(gnd_idx);
-        
+
         let _ = graph.add_node("extra".to_string());
-        
+
         prop_assert!(graph.is_ground(gnd_idx));
     }
 }
 
 }
+
+fn resistor(r: f64) -> ComponentKind {
+    ComponentKind::Resistor { r: Resistance::known(r).unwrap() }
+}
+
+/// A circuit with every node reachable from ground through active
+/// components forms a single connected component.
+#[test]
+fn connected_components_reports_one_group_for_a_fully_wired_circuit() {
+    let mut graph = CircuitGraph::new();
+    let a = graph.add_node("a".to_string());
+    let b = graph.add_node("b".to_string());
+    let gnd = graph.add_node("0".to_string());
+    graph.add_component("R1".to_string(), resistor(1e3), (a, b));
+    graph.add_component("R2".to_string(), resistor(1e3), (b, gnd));
+    graph.set_ground(gnd);
+
+    assert_eq!(graph.connected_components().len(), 1);
+    assert!(graph.is_fully_connected_to_ground());
+}
+
+/// A node with no active connections at all forms its own singleton
+/// group and breaks full connectivity to ground.
+#[test]
+fn an_isolated_node_forms_its_own_singleton_component() {
+    let mut graph = CircuitGraph::new();
+    let a = graph.add_node("a".to_string());
+    let gnd = graph.add_node("0".to_string());
+    let floating = graph.add_node("floating".to_string());
+    graph.add_component("R1".to_string(), resistor(1e3), (a, gnd));
+    graph.set_ground(gnd);
+
+    let groups = graph.connected_components();
+    assert_eq!(groups.len(), 2);
+    assert!(groups.iter().any(|g| g == &vec![floating]));
+    assert!(!graph.is_fully_connected_to_ground());
+}
+
+/// A current source still counts as a galvanic connection for
+/// connectivity purposes, even though it behaves as an infinite
+/// impedance in the actual circuit solve.
+#[test]
+fn a_current_source_counts_as_a_connectivity_edge() {
+    let mut graph = CircuitGraph::new();
+    let a = graph.add_node("a".to_string());
+    let gnd = graph.add_node("0".to_string());
+    graph.add_component("I1".to_string(), ComponentKind::CurrentSource { i: Current::dc(1e-3) }, (a, gnd));
+    graph.set_ground(gnd);
+
+    assert_eq!(graph.connected_components().len(), 1);
+    assert!(graph.is_fully_connected_to_ground());
+}
+
+/// With no ground node set, full connectivity is never reported, even
+/// for an otherwise fully wired circuit.
+#[test]
+fn is_fully_connected_to_ground_is_false_with_no_ground_set() {
+    let mut graph = CircuitGraph::new();
+    let a = graph.add_node("a".to_string());
+    let b = graph.add_node("b".to_string());
+    graph.add_component("R1".to_string(), resistor(1e3), (a, b));
+
+    assert!(!graph.is_fully_connected_to_ground());
+}
+
+/// A tree circuit (no cycles at all) has no chords, so it contributes no
+/// fundamental loops.
+#[test]
+fn a_tree_shaped_circuit_has_no_fundamental_loops() {
+    let mut graph = CircuitGraph::new();
+    let a = graph.add_node("a".to_string());
+    let b = graph.add_node("b".to_string());
+    let gnd = graph.add_node("0".to_string());
+    graph.add_component("R1".to_string(), resistor(1e3), (a, b));
+    graph.add_component("R2".to_string(), resistor(1e3), (b, gnd));
+
+    assert!(graph.fundamental_loops().is_empty());
+}
+
+/// A single triangle of three resistors has exactly one chord and so
+/// exactly one fundamental loop, made up of all three components.
+#[test]
+fn a_single_triangle_yields_one_fundamental_loop_covering_all_three_edges() {
+    let mut graph = CircuitGraph::new();
+    let a = graph.add_node("a".to_string());
+    let b = graph.add_node("b".to_string());
+    let c = graph.add_node("c".to_string());
+    let r1 = graph.add_component("R1".to_string(), resistor(1e3), (a, b));
+    let r2 = graph.add_component("R2".to_string(), resistor(1e3), (b, c));
+    let r3 = graph.add_component("R3".to_string(), resistor(1e3), (c, a));
+
+    let loops = graph.fundamental_loops();
+    assert_eq!(loops.len(), 1);
+    let touched: std::collections::HashSet<_> = loops[0].iter().map(|(idx, _)| *idx).collect();
+    assert_eq!(touched, [r1, r2, r3].into_iter().collect());
+}
+
+/// The number of fundamental loops matches `E - N + components`, the
+/// standard count of independent KVL equations, across a circuit with
+/// more than one chord.
+#[test]
+fn fundamental_loop_count_matches_e_minus_n_plus_components() {
+    let mut graph = CircuitGraph::new();
+    let a = graph.add_node("a".to_string());
+    let b = graph.add_node("b".to_string());
+    let c = graph.add_node("c".to_string());
+    let d = graph.add_node("d".to_string());
+    graph.add_component("R1".to_string(), resistor(1e3), (a, b));
+    graph.add_component("R2".to_string(), resistor(1e3), (b, c));
+    graph.add_component("R3".to_string(), resistor(1e3), (c, d));
+    graph.add_component("R4".to_string(), resistor(1e3), (d, a));
+    graph.add_component("R5".to_string(), resistor(1e3), (a, c));
+
+    let expected = graph.active_component_count() - graph.nodes.len() + graph.connected_components().len();
+    assert_eq!(graph.fundamental_loops().len(), expected);
+}
+
+/// A component deactivated (e.g. by a prior reduction pass) is excluded
+/// from the spanning forest and from every loop.
+#[test]
+fn inactive_components_are_ignored_by_fundamental_loops() {
+    let mut graph = CircuitGraph::new();
+    let a = graph.add_node("a".to_string());
+    let b = graph.add_node("b".to_string());
+    let c = graph.add_node("c".to_string());
+    graph.add_component("R1".to_string(), resistor(1e3), (a, b));
+    graph.add_component("R2".to_string(), resistor(1e3), (b, c));
+    let r3 = graph.add_component("R3".to_string(), resistor(1e3), (c, a));
+    graph.components[r3].is_active = false;
+
+    assert!(graph.fundamental_loops().is_empty());
+}
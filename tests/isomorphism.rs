@@ -0,0 +1,178 @@
+use electro_solve::units::*;
+use electro_solve::component::*;
+use electro_solve::graph::*;
+
+/// A circuit is trivially isomorphic to itself, via the identity
+/// permutation (or an equivalent one, when symmetry allows more than
+/// one).
+#[test]
+fn a_circuit_is_isomorphic_to_itself() {
+    let mut graph = CircuitGraph::new();
+    let a = graph.add_node("a".to_string());
+    let b = graph.add_node("b".to_string());
+    graph.set_ground(a);
+    graph.add_component("R1".to_string(), ComponentKind::Resistor { r: Resistance::known(100.0).unwrap() }, (a, b));
+
+    let mapping = graph.is_isomorphic_to(&graph).unwrap();
+
+    assert_eq!(mapping[a], a);
+    assert_eq!(mapping[b], b);
+}
+
+/// Relabeling every node (and renumbering components to match) produces
+/// a graph that's still isomorphic to the original, via the permutation
+/// that undoes the relabeling.
+#[test]
+fn renumbering_nodes_preserves_isomorphism() {
+    let mut original = CircuitGraph::new();
+    let a = original.add_node("a".to_string());
+    let b = original.add_node("b".to_string());
+    let c = original.add_node("c".to_string());
+    original.set_ground(a);
+    original.add_component("R1".to_string(), ComponentKind::Resistor { r: Resistance::known(100.0).unwrap() }, (a, b));
+    original.add_component("R2".to_string(), ComponentKind::Resistor { r: Resistance::known(200.0).unwrap() }, (b, c));
+
+    // Build the same circuit with nodes added in reverse order: c, b, a.
+    let mut relabeled = CircuitGraph::new();
+    let c2 = relabeled.add_node("c".to_string());
+    let b2 = relabeled.add_node("b".to_string());
+    let a2 = relabeled.add_node("a".to_string());
+    relabeled.set_ground(a2);
+    relabeled.add_component("R2".to_string(), ComponentKind::Resistor { r: Resistance::known(200.0).unwrap() }, (b2, c2));
+    relabeled.add_component("R1".to_string(), ComponentKind::Resistor { r: Resistance::known(100.0).unwrap() }, (a2, b2));
+
+    let mapping = original.is_isomorphic_to(&relabeled).unwrap();
+
+    assert_eq!(mapping[a], a2);
+    assert_eq!(mapping[b], b2);
+    assert_eq!(mapping[c], c2);
+}
+
+/// A series/parallel reduction pass preserves the circuit up to a
+/// coarser node set -- so two DIFFERENT node counts are never
+/// isomorphic, even if the reduced circuit is "equivalent" in an
+/// electrical sense.
+#[test]
+fn graphs_with_different_node_counts_are_never_isomorphic() {
+    let mut a = CircuitGraph::new();
+    let a0 = a.add_node("a".to_string());
+    let a1 = a.add_node("b".to_string());
+    a.add_component("R1".to_string(), ComponentKind::Resistor { r: Resistance::known(100.0).unwrap() }, (a0, a1));
+
+    let mut b = CircuitGraph::new();
+    let b0 = b.add_node("a".to_string());
+    let b1 = b.add_node("b".to_string());
+    let b2 = b.add_node("c".to_string());
+    b.add_component("R1".to_string(), ComponentKind::Resistor { r: Resistance::known(50.0).unwrap() }, (b0, b1));
+    b.add_component("R2".to_string(), ComponentKind::Resistor { r: Resistance::known(50.0).unwrap() }, (b1, b2));
+
+    assert!(a.is_isomorphic_to(&b).is_none());
+}
+
+/// Mismatched component values at otherwise identical positions break
+/// the isomorphism -- structure alone isn't enough, the parameter values
+/// must match too.
+#[test]
+fn mismatched_component_values_break_the_isomorphism() {
+    let mut a = CircuitGraph::new();
+    let a0 = a.add_node("a".to_string());
+    let a1 = a.add_node("b".to_string());
+    a.add_component("R1".to_string(), ComponentKind::Resistor { r: Resistance::known(100.0).unwrap() }, (a0, a1));
+
+    let mut b = CircuitGraph::new();
+    let b0 = b.add_node("a".to_string());
+    let b1 = b.add_node("b".to_string());
+    b.add_component("R1".to_string(), ComponentKind::Resistor { r: Resistance::known(200.0).unwrap() }, (b0, b1));
+
+    assert!(a.is_isomorphic_to(&b).is_none());
+}
+
+/// A resistor and an inductor between the same pair of nodes are
+/// different kinds, even with the same node topology -- not isomorphic.
+#[test]
+fn mismatched_component_kinds_break_the_isomorphism() {
+    let mut a = CircuitGraph::new();
+    let a0 = a.add_node("a".to_string());
+    let a1 = a.add_node("b".to_string());
+    a.add_component("R1".to_string(), ComponentKind::Resistor { r: Resistance::known(100.0).unwrap() }, (a0, a1));
+
+    let mut b = CircuitGraph::new();
+    let b0 = b.add_node("a".to_string());
+    let b1 = b.add_node("b".to_string());
+    b.add_component("L1".to_string(), ComponentKind::Inductor { l: Inductance::known(100.0).unwrap() }, (b0, b1));
+
+    assert!(a.is_isomorphic_to(&b).is_none());
+}
+
+/// Ground must map to ground: a chain of distinct resistor values has no
+/// structural symmetry to exploit, so grounding the opposite end breaks
+/// the isomorphism even though the chain's component values still line
+/// up node-for-node.
+#[test]
+fn ground_must_map_to_ground() {
+    let mut a = CircuitGraph::new();
+    let a0 = a.add_node("a".to_string());
+    let a1 = a.add_node("b".to_string());
+    let a2 = a.add_node("c".to_string());
+    let a3 = a.add_node("d".to_string());
+    a.set_ground(a0);
+    a.add_component("R1".to_string(), ComponentKind::Resistor { r: Resistance::known(100.0).unwrap() }, (a0, a1));
+    a.add_component("R2".to_string(), ComponentKind::Resistor { r: Resistance::known(200.0).unwrap() }, (a1, a2));
+    a.add_component("R3".to_string(), ComponentKind::Resistor { r: Resistance::known(300.0).unwrap() }, (a2, a3));
+
+    let mut b = CircuitGraph::new();
+    let b0 = b.add_node("a".to_string());
+    let b1 = b.add_node("b".to_string());
+    let b2 = b.add_node("c".to_string());
+    let b3 = b.add_node("d".to_string());
+    b.set_ground(b3);
+    b.add_component("R1".to_string(), ComponentKind::Resistor { r: Resistance::known(100.0).unwrap() }, (b0, b1));
+    b.add_component("R2".to_string(), ComponentKind::Resistor { r: Resistance::known(200.0).unwrap() }, (b1, b2));
+    b.add_component("R3".to_string(), ComponentKind::Resistor { r: Resistance::known(300.0).unwrap() }, (b2, b3));
+
+    assert!(a.is_isomorphic_to(&b).is_none());
+}
+
+/// Two resistors in parallel between the same node pair in one graph
+/// must match two resistors in parallel (not e.g. one resistor plus one
+/// inductor) in the other -- the per-pair component multiset must match
+/// exactly.
+#[test]
+fn parallel_component_multisets_must_match_exactly() {
+    let mut a = CircuitGraph::new();
+    let a0 = a.add_node("a".to_string());
+    let a1 = a.add_node("b".to_string());
+    a.add_component("R1".to_string(), ComponentKind::Resistor { r: Resistance::known(100.0).unwrap() }, (a0, a1));
+    a.add_component("R2".to_string(), ComponentKind::Resistor { r: Resistance::known(200.0).unwrap() }, (a0, a1));
+
+    let mut b = CircuitGraph::new();
+    let b0 = b.add_node("a".to_string());
+    let b1 = b.add_node("b".to_string());
+    b.add_component("R1".to_string(), ComponentKind::Resistor { r: Resistance::known(100.0).unwrap() }, (b0, b1));
+    b.add_component("L1".to_string(), ComponentKind::Inductor { l: Inductance::known(200.0).unwrap() }, (b0, b1));
+
+    assert!(a.is_isomorphic_to(&b).is_none());
+}
+
+/// An inactive component (deactivated by a reduction pass) is ignored by
+/// the comparison, so a reduced circuit can be isomorphic to a
+/// differently-reduced one as long as their active topologies agree.
+#[test]
+fn inactive_components_are_ignored() {
+    let mut a = CircuitGraph::new();
+    let a0 = a.add_node("a".to_string());
+    let a1 = a.add_node("b".to_string());
+    let stale = a.add_component("R1".to_string(), ComponentKind::Resistor { r: Resistance::known(999.0).unwrap() }, (a0, a1));
+    a.components[stale].is_active = false;
+    a.add_component("REQ".to_string(), ComponentKind::Resistor { r: Resistance::known(100.0).unwrap() }, (a0, a1));
+
+    let mut b = CircuitGraph::new();
+    let b0 = b.add_node("a".to_string());
+    let b1 = b.add_node("b".to_string());
+    b.add_component("REQ".to_string(), ComponentKind::Resistor { r: Resistance::known(100.0).unwrap() }, (b0, b1));
+
+    let mapping = a.is_isomorphic_to(&b).unwrap();
+
+    assert_eq!(mapping[a0], b0);
+    assert_eq!(mapping[a1], b1);
+}
@@ -0,0 +1,90 @@
+use num_complex::Complex64;
+
+use electro_solve::component::ComponentKind;
+use electro_solve::graph::CircuitGraph;
+use electro_solve::mna::equivalent_impedance;
+use electro_solve::units::*;
+
+mod common;
+use common::*;
+
+#[test]
+fn matches_series_parallel_on_a_series_chain() {
+    let mut graph = CircuitGraph::new();
+    let values = [100.0, 220.0, 330.0];
+    build_series_chain(&mut graph, &values);
+
+    let omega = AngularFrequency::new(1.0).unwrap();
+    let expected = series_impedance(&values);
+
+    let z = equivalent_impedance(&graph, omega, 0, values.len() - 1);
+    assert_impedance_eq(z, expected, EPSILON_PHYSICAL);
+}
+
+#[test]
+fn matches_series_parallel_on_a_parallel_bank() {
+    let mut graph = CircuitGraph::new();
+    graph.add_node("n0".to_string());
+    graph.add_node("n1".to_string());
+    graph.set_ground(0);
+
+    let values = [1000.0, 2000.0, 4000.0];
+    for (i, &r) in values.iter().enumerate() {
+        create_resistor(&mut graph, &format!("R{i}"), r, 0, 1);
+    }
+
+    let omega = AngularFrequency::new(1.0).unwrap();
+    let impedances: Vec<_> = values
+        .iter()
+        .map(|&r| ImpedanceResult::new_finite(Complex64::new(r, 0.0)))
+        .collect();
+    let expected = combine_parallel_many(&impedances);
+
+    let z = equivalent_impedance(&graph, omega, 0, 1);
+    assert_impedance_eq(z, expected, EPSILON_PHYSICAL);
+}
+
+/// A balanced Wheatstone bridge has no current through the bridge
+/// resistor, so the equivalent impedance equals two parallel series-pairs
+/// -- something series/parallel reduction alone cannot discover because
+/// no two resistors here are purely in series or purely in parallel.
+#[test]
+fn solves_a_balanced_wheatstone_bridge() {
+    let mut graph = CircuitGraph::new();
+    let a = graph.add_node("a".to_string()); // positive terminal
+    let b = graph.add_node("b".to_string()); // negative terminal
+    let top = graph.add_node("top".to_string());
+    let bottom = graph.add_node("bottom".to_string());
+    graph.set_ground(b);
+
+    create_resistor(&mut graph, "R1", 100.0, a, top);
+    create_resistor(&mut graph, "R2", 200.0, a, bottom);
+    create_resistor(&mut graph, "R3", 100.0, top, b);
+    create_resistor(&mut graph, "R4", 200.0, bottom, b);
+    create_resistor(&mut graph, "Rg", 500.0, top, bottom); // the bridge
+
+    let omega = AngularFrequency::new(1.0).unwrap();
+    let z = equivalent_impedance(&graph, omega, a, b);
+
+    // Balanced bridge: 200 Ohm (R1 + R3) and 400 Ohm (R2 + R4) series legs
+    // in parallel = 400/3 Ohm, independent of the galvanometer/bridge
+    // resistor's value.
+    let expected = ImpedanceResult::new_finite(Complex64::new(400.0 / 3.0, 0.0));
+    assert_impedance_eq(z, expected, EPSILON_PHYSICAL);
+}
+
+#[test]
+fn floating_subnetwork_reports_open() {
+    let mut graph = CircuitGraph::new();
+    let a = graph.add_node("a".to_string());
+    let b = graph.add_node("b".to_string());
+    let c = graph.add_node("c".to_string());
+    graph.set_ground(a);
+    create_resistor(&mut graph, "R1", 100.0, a, b);
+    // `c` has no path to ground or to the other terminal.
+    let _ = c;
+
+    let omega = AngularFrequency::new(1.0).unwrap();
+    let z = equivalent_impedance(&graph, omega, b, c);
+    assert!(z.is_open());
+}
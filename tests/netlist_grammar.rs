@@ -0,0 +1,54 @@
+use electro_solve::component::ComponentKind;
+use electro_solve::netlist::parse;
+use electro_solve::units::Value;
+
+#[test]
+fn a_trailing_semicolon_comment_is_ignored() {
+    let text = "R1 a 0 100 ; half of the divider\n";
+    let graph = parse(text).unwrap();
+    assert_eq!(graph.components.len(), 1);
+    match &graph.components[0].kind {
+        ComponentKind::Resistor { r } => assert_eq!(r.0, Value::Known(100.0)),
+        other => panic!("expected a resistor, got {other:?}"),
+    }
+}
+
+#[test]
+fn a_plus_prefixed_continuation_line_joins_with_the_previous_line() {
+    let text = "R1 a\n+ 0 100\n";
+    let graph = parse(text).unwrap();
+    assert_eq!(graph.components.len(), 1);
+    assert_eq!(graph.nodes.len(), 2);
+}
+
+#[test]
+fn directives_are_recognized_case_insensitively_and_ignored() {
+    let text = "R1 a 0 100\n.End\n";
+    let graph = parse(text).unwrap();
+    assert_eq!(graph.components.len(), 1);
+
+    let text_lower = "R1 a 0 100\n.end\n";
+    let graph_lower = parse(text_lower).unwrap();
+    assert_eq!(graph_lower.components.len(), 1);
+}
+
+#[test]
+fn a_short_component_line_reports_the_column_of_the_component_id() {
+    let text = "R1 a 0\n";
+    let err = parse(text).unwrap_err();
+    assert_eq!(err.line, 1);
+    assert_eq!(err.column, 1);
+}
+
+#[test]
+fn an_empty_directive_name_is_rejected() {
+    let text = "R1 a 0 100\n.\n";
+    assert!(parse(text).is_err());
+}
+
+#[test]
+fn a_star_comment_line_is_skipped_regardless_of_leading_whitespace() {
+    let text = "   * indented comment\nR1 a 0 100\n";
+    let graph = parse(text).unwrap();
+    assert_eq!(graph.components.len(), 1);
+}
@@ -0,0 +1,77 @@
+use proptest::prelude::*;
+
+use electro_solve::component::ComponentKind;
+use electro_solve::netlist::{parse, to_netlist};
+use electro_solve::units::Value;
+
+mod common;
+use common::strategies::arbitrary_circuit_graph;
+
+/// Rounds a phasor to a fixed number of significant digits (via scientific
+/// notation, so it behaves the same across magnitudes) before it goes into
+/// a [`kind_signature`] -- an AC phasor round-trips through a magnitude/
+/// phase-angle representation in `to_netlist`/`lower_component`, and `cos`/
+/// `sin` don't invert bit-exactly, so comparing full `Debug` precision
+/// would fail on floating-point noise well below anything physically
+/// meaningful.
+fn phasor_signature(z: num_complex::Complex64) -> String {
+    format!("{:.9e}+{:.9e}i", z.re, z.im)
+}
+
+/// A component's kind and value, independent of its node indices -- node
+/// indices are free to be renumbered by a re-parse, so this is what
+/// "equivalent graph" means for the round-trip check below.
+fn kind_signature(kind: &ComponentKind) -> String {
+    match kind {
+        ComponentKind::Resistor { r } => format!("R{:?}", r.0),
+        ComponentKind::Inductor { l } => format!("L{:?}", l.0),
+        ComponentKind::Capacitor { c } => format!("C{:?}", c.0),
+        ComponentKind::VoltageSource { v } => format!("V{}", phasor_signature(v.0)),
+        ComponentKind::CurrentSource { i } => format!("I{}", phasor_signature(i.0)),
+        ComponentKind::Impedance { z } => format!("Z{:?}", z),
+        ComponentKind::VCVS { gain, control_nodes } => format!("E{gain:?}{control_nodes:?}"),
+        ComponentKind::VCCS { gain, control_nodes } => format!("G{gain:?}{control_nodes:?}"),
+        ComponentKind::CCVS { gain, control_component } => format!("H{gain:?}{control_component:?}"),
+        ComponentKind::CCCS { gain, control_component } => format!("F{gain:?}{control_component:?}"),
+    }
+}
+
+proptest! {
+    #[test]
+    fn parse_of_to_netlist_reproduces_an_equivalent_graph(
+        mut graph in arbitrary_circuit_graph()
+    ) {
+        // The rendered text only mentions nodes that a component touches,
+        // so round-tripping needs the chosen ground node to actually be
+        // wired into the circuit.
+        prop_assume!(graph.components.iter().any(|c| c.nodes.0 == 0 || c.nodes.1 == 0));
+        graph.set_ground(0);
+        let text = to_netlist(&graph);
+
+        let reparsed = parse(&text).expect("round-tripped netlist text should reparse");
+
+        // Nodes with no components referencing them don't appear in the
+        // rendered text at all, so only the component-level structure
+        // (which fully determines the equivalent circuit) is checked here.
+        prop_assert_eq!(reparsed.components.len(), graph.components.len());
+
+        let mut original: Vec<String> = graph.components.iter().map(|c| kind_signature(&c.kind)).collect();
+        let mut reparsed_kinds: Vec<String> = reparsed.components.iter().map(|c| kind_signature(&c.kind)).collect();
+        original.sort();
+        reparsed_kinds.sort();
+        prop_assert_eq!(original, reparsed_kinds);
+    }
+}
+
+#[test]
+fn bare_identifier_value_round_trips_as_unknown() {
+    let text = "R1 a 0 Rload\n.end";
+    let graph = parse(text).unwrap();
+    match &graph.components[0].kind {
+        ComponentKind::Resistor { r } => assert_eq!(r.0, Value::Unknown("Rload".to_string())),
+        other => panic!("expected a resistor, got {other:?}"),
+    }
+
+    let rendered = to_netlist(&graph);
+    assert!(rendered.contains("Rload"));
+}
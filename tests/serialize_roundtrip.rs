@@ -0,0 +1,114 @@
+use proptest::prelude::*;
+
+use electro_solve::graph::CircuitGraph;
+use electro_solve::mna::equivalent_impedance;
+use electro_solve::reduce::reduce;
+use electro_solve::serialize::{
+    read_reduction_steps, write_reduction_steps, replay, read_solved, write_solved,
+    GRAPH_FORMAT_VERSION, GRAPH_MAGIC,
+};
+use electro_solve::units::*;
+
+mod common;
+use common::strategies::arbitrary_circuit_graph;
+use common::*;
+
+proptest! {
+    #[test]
+    fn read_of_write_reproduces_an_equal_graph(graph in arbitrary_circuit_graph()) {
+        let mut bytes = Vec::new();
+        graph.write(&mut bytes).unwrap();
+
+        let read_back = CircuitGraph::read(&mut bytes.as_slice()).unwrap();
+        prop_assert_eq!(read_back, graph);
+    }
+}
+
+/// A balanced Wheatstone bridge needs a delta-wye step as well as
+/// series/parallel ones, so replaying its trace exercises every
+/// `ReductionStep` variant's codec.
+#[test]
+fn replaying_a_saved_reduction_trace_reproduces_the_equivalent_impedance() {
+    let mut graph = CircuitGraph::new();
+    let a = graph.add_node("a".to_string());
+    let b = graph.add_node("b".to_string());
+    let top = graph.add_node("top".to_string());
+    let bottom = graph.add_node("bottom".to_string());
+    graph.set_ground(b);
+
+    create_resistor(&mut graph, "R1", 100.0, a, top);
+    create_resistor(&mut graph, "R2", 200.0, a, bottom);
+    create_resistor(&mut graph, "R3", 100.0, top, b);
+    create_resistor(&mut graph, "R4", 200.0, bottom, b);
+    create_resistor(&mut graph, "Rg", 500.0, top, bottom);
+
+    let omega = AngularFrequency::new(1.0).unwrap();
+    let expected = equivalent_impedance(&graph, omega, a, b);
+
+    let mut reduced = graph.clone();
+    let steps = reduce(&mut reduced, omega).unwrap();
+    assert!(steps.iter().any(|s| matches!(s, electro_solve::reduce::ReductionStep::DeltaWye { .. })));
+
+    let mut bytes = Vec::new();
+    write_reduction_steps(&steps, &mut bytes).unwrap();
+    let decoded_steps = read_reduction_steps(&mut bytes.as_slice()).unwrap();
+
+    let mut replayed = graph.clone();
+    replay(&mut replayed, &decoded_steps, omega).unwrap();
+
+    let actual = equivalent_impedance(&replayed, omega, a, b);
+    assert_impedance_eq(actual, expected, EPSILON_PHYSICAL);
+}
+
+/// `write_solved`/`read_solved` bundle the graph and its trace into one
+/// blob; reading it back should reproduce both independently of how
+/// `write_reduction_steps`/`replay` are exercised above.
+#[test]
+fn write_solved_round_trips_both_the_graph_and_its_reduction_trace() {
+    let mut graph = CircuitGraph::new();
+    let a = graph.add_node("a".to_string());
+    let b = graph.add_node("b".to_string());
+    graph.set_ground(b);
+    create_resistor(&mut graph, "R1", 100.0, a, b);
+    create_resistor(&mut graph, "R2", 200.0, a, b);
+
+    let omega = AngularFrequency::new(1.0).unwrap();
+    let steps = reduce(&mut graph, omega).unwrap();
+    assert!(steps.iter().any(|s| matches!(s, electro_solve::reduce::ReductionStep::Parallel { .. })));
+
+    let mut bytes = Vec::new();
+    write_solved(&graph, &steps, &mut bytes).unwrap();
+
+    let (read_graph, read_steps) = read_solved(&mut bytes.as_slice()).unwrap();
+    assert_eq!(read_graph, graph);
+    assert_eq!(read_steps, steps);
+}
+
+#[test]
+fn write_stamps_the_magic_header_and_version_byte() {
+    let mut graph = CircuitGraph::new();
+    graph.add_node("a".to_string());
+    graph.add_node("b".to_string());
+    graph.set_ground(1);
+    create_resistor(&mut graph, "R1", 10.0, 0, 1);
+
+    let mut bytes = Vec::new();
+    graph.write(&mut bytes).unwrap();
+
+    assert_eq!(&bytes[0..4], &GRAPH_MAGIC);
+    assert_eq!(bytes[4], GRAPH_FORMAT_VERSION);
+}
+
+#[test]
+fn read_rejects_data_with_the_wrong_magic() {
+    let garbage = b"NOPE\x01\x00\x00\x00\x00";
+    assert!(CircuitGraph::read(&mut garbage.as_slice()).is_err());
+}
+
+#[test]
+fn read_rejects_an_unknown_format_version() {
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&GRAPH_MAGIC);
+    bytes.push(GRAPH_FORMAT_VERSION.wrapping_add(1));
+    assert!(CircuitGraph::read(&mut bytes.as_slice()).is_err());
+}
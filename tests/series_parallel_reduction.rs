@@ -0,0 +1,182 @@
+use proptest::prelude::*;
+
+use electro_solve::units::*;
+use electro_solve::component::*;
+use electro_solve::graph::*;
+
+mod common;
+use common::*;
+
+fn resistance_value(kind: &ComponentKind) -> f64 {
+    match kind {
+        ComponentKind::Resistor { r } => Option::<f64>::from(r.clone()).unwrap(),
+        other => panic!("expected a Resistor, got {other:?}"),
+    }
+}
+
+fn active_component(graph: &CircuitGraph) -> &CircuitComponent {
+    graph.components.iter().find(|c| c.is_active).expect("exactly one active component should remain")
+}
+
+proptest! {
+
+#[test]
+fn prop_series_chain_of_resistors_collapses_to_their_sum(
+    values in prop::collection::vec(1.0_f64..1e6_f64, 2..8)
+) {
+    let mut graph = CircuitGraph::new();
+    let nodes: Vec<_> = (0..=values.len()).map(|i| graph.add_node(format!("n{i}"))).collect();
+    graph.set_ground(nodes[0]);
+    let indices: Vec<_> = values
+        .iter()
+        .enumerate()
+        .map(|(i, &r)| create_resistor(&mut graph, &format!("R{i}"), r, nodes[i], nodes[i + 1]))
+        .collect();
+
+    let equivalent_of = graph.reduce_series_parallel();
+
+    prop_assert_eq!(graph.active_component_count(), 1);
+    let expected: f64 = values.iter().sum();
+    prop_assert!((resistance_value(&active_component(&graph).kind) - expected).abs() / expected < EPSILON_PHYSICAL);
+
+    for idx in indices {
+        prop_assert!(equivalent_of.contains_key(&idx));
+    }
+}
+
+#[test]
+fn prop_parallel_bank_of_resistors_collapses_to_the_reciprocal_sum(
+    values in prop::collection::vec(1.0_f64..1e6_f64, 2..5)
+) {
+    let mut graph = CircuitGraph::new();
+    let n0 = graph.add_node("n0".to_string());
+    let n1 = graph.add_node("n1".to_string());
+    let indices: Vec<_> = values.iter().enumerate().map(|(i, &r)| create_resistor(&mut graph, &format!("R{i}"), r, n0, n1)).collect();
+
+    let equivalent_of = graph.reduce_series_parallel();
+
+    prop_assert_eq!(graph.active_component_count(), 1);
+    let expected = 1.0 / values.iter().map(|r| 1.0 / r).sum::<f64>();
+    prop_assert!((resistance_value(&active_component(&graph).kind) - expected).abs() / expected < EPSILON_PHYSICAL);
+
+    for idx in indices {
+        prop_assert!(equivalent_of.contains_key(&idx));
+    }
+}
+
+}
+
+/// Inductors in series add, same as resistors.
+#[test]
+fn inductors_in_series_sum() {
+    let mut graph = CircuitGraph::new();
+    let a = graph.add_node("a".to_string());
+    let b = graph.add_node("b".to_string());
+    let c = graph.add_node("c".to_string());
+    graph.add_component("L1".to_string(), ComponentKind::Inductor { l: Inductance::known(1e-3).unwrap() }, (a, b));
+    graph.add_component("L2".to_string(), ComponentKind::Inductor { l: Inductance::known(2e-3).unwrap() }, (b, c));
+
+    graph.reduce_series_parallel();
+
+    assert_eq!(graph.active_component_count(), 1);
+    match &active_component(&graph).kind {
+        ComponentKind::Inductor { l } => assert!((Option::<f64>::from(l.clone()).unwrap() - 3e-3).abs() < 1e-12),
+        other => panic!("expected an Inductor, got {other:?}"),
+    }
+}
+
+/// Capacitors in series combine via the reciprocal-sum rule, the inverse
+/// of how they combine in parallel.
+#[test]
+fn capacitors_in_series_combine_via_reciprocal_sum() {
+    let mut graph = CircuitGraph::new();
+    let a = graph.add_node("a".to_string());
+    let b = graph.add_node("b".to_string());
+    let c = graph.add_node("c".to_string());
+    graph.add_component("C1".to_string(), ComponentKind::Capacitor { c: Capacitance::known(1e-6).unwrap() }, (a, b));
+    graph.add_component("C2".to_string(), ComponentKind::Capacitor { c: Capacitance::known(1e-6).unwrap() }, (b, c));
+
+    graph.reduce_series_parallel();
+
+    assert_eq!(graph.active_component_count(), 1);
+    match &active_component(&graph).kind {
+        ComponentKind::Capacitor { c } => assert!((Option::<f64>::from(c.clone()).unwrap() - 0.5e-6).abs() < 1e-15),
+        other => panic!("expected a Capacitor, got {other:?}"),
+    }
+}
+
+/// Capacitors in parallel simply add, the inverse of how they combine in
+/// series.
+#[test]
+fn capacitors_in_parallel_sum() {
+    let mut graph = CircuitGraph::new();
+    let a = graph.add_node("a".to_string());
+    let b = graph.add_node("b".to_string());
+    graph.add_component("C1".to_string(), ComponentKind::Capacitor { c: Capacitance::known(1e-6).unwrap() }, (a, b));
+    graph.add_component("C2".to_string(), ComponentKind::Capacitor { c: Capacitance::known(2e-6).unwrap() }, (a, b));
+
+    graph.reduce_series_parallel();
+
+    assert_eq!(graph.active_component_count(), 1);
+    match &active_component(&graph).kind {
+        ComponentKind::Capacitor { c } => assert!((Option::<f64>::from(c.clone()).unwrap() - 3e-6).abs() < 1e-15),
+        other => panic!("expected a Capacitor, got {other:?}"),
+    }
+}
+
+/// A degree-2 node does not eliminate its two components if they're
+/// different passive kinds -- an R and an L in series stay separate since
+/// there's no single equivalent component type for a mixed pair.
+#[test]
+fn mismatched_kinds_at_a_degree_two_node_are_left_alone() {
+    let mut graph = CircuitGraph::new();
+    let a = graph.add_node("a".to_string());
+    let b = graph.add_node("b".to_string());
+    let c = graph.add_node("c".to_string());
+    graph.add_component("R1".to_string(), ComponentKind::Resistor { r: Resistance::known(100.0).unwrap() }, (a, b));
+    graph.add_component("L1".to_string(), ComponentKind::Inductor { l: Inductance::known(1e-3).unwrap() }, (b, c));
+
+    let equivalent_of = graph.reduce_series_parallel();
+
+    assert_eq!(graph.active_component_count(), 2);
+    assert!(equivalent_of.is_empty());
+}
+
+/// The ground node is never spliced out by the series rule, even if it
+/// happens to have degree exactly 2 with two same-kind neighbors -- a
+/// ground reference has to stay addressable for nodal/mesh analysis.
+#[test]
+fn ground_node_is_never_eliminated_by_the_series_rule() {
+    let mut graph = CircuitGraph::new();
+    let a = graph.add_node("a".to_string());
+    let gnd = graph.add_node("0".to_string());
+    let c = graph.add_node("c".to_string());
+    graph.set_ground(gnd);
+    graph.add_component("R1".to_string(), ComponentKind::Resistor { r: Resistance::known(100.0).unwrap() }, (a, gnd));
+    graph.add_component("R2".to_string(), ComponentKind::Resistor { r: Resistance::known(200.0).unwrap() }, (gnd, c));
+
+    let equivalent_of = graph.reduce_series_parallel();
+
+    assert_eq!(graph.active_component_count(), 2);
+    assert!(equivalent_of.is_empty());
+}
+
+/// Chained merges resolve transitively: when a first merge's equivalent
+/// is itself later folded into a second merge, every original component
+/// maps straight to the final survivor, not to the intermediate one.
+#[test]
+fn equivalent_of_resolves_transitively_through_chained_merges() {
+    let mut graph = CircuitGraph::new();
+    let a = graph.add_node("a".to_string());
+    let b = graph.add_node("b".to_string());
+    let c = graph.add_node("c".to_string());
+    let r1 = graph.add_component("R1".to_string(), ComponentKind::Resistor { r: Resistance::known(100.0).unwrap() }, (a, b));
+    let r2 = graph.add_component("R2".to_string(), ComponentKind::Resistor { r: Resistance::known(200.0).unwrap() }, (b, c));
+
+    let equivalent_of = graph.reduce_series_parallel();
+
+    assert_eq!(graph.active_component_count(), 1);
+    let survivor = equivalent_of[&r1];
+    assert_eq!(equivalent_of[&r2], survivor);
+    assert!(graph.components[survivor].is_active);
+}
@@ -0,0 +1,127 @@
+use electro_solve::component::ComponentKind;
+use electro_solve::netlist::parse;
+
+/// A `.subckt`/`.ends` block instantiated with `X` is flattened into
+/// plain components, with each one's id scoped under the instance so it
+/// stays unique alongside anything else in the circuit.
+#[test]
+fn simple_two_port_subckt_instantiates_and_flattens() {
+    let text = "\
+.subckt RDIV in out
+R1 in out 1k
+R2 out 0 1k
+.ends
+Vin a 0 5
+X1 a b RDIV
+Rload b 0 1M
+.end";
+    let graph = parse(text).unwrap();
+    let ids: Vec<&str> = graph.components.iter().map(|c| c.id.as_str()).collect();
+    assert!(ids.contains(&"R1@X1"));
+    assert!(ids.contains(&"R2@X1"));
+    assert_eq!(graph.components.len(), 4);
+}
+
+/// Two instances of the same subcircuit must not collide: the node
+/// between their two internal resistors is uniquified per instance.
+#[test]
+fn two_instances_of_the_same_subckt_get_distinct_internal_nodes() {
+    let text = "\
+.subckt RDIV in out
+R1 in mid 1k
+R2 mid out 1k
+.ends
+Vin a 0 5
+X1 a b RDIV
+X2 b c RDIV
+Rload c 0 1M
+.end";
+    let graph = parse(text).unwrap();
+    let x1_mid = graph.node(graph.components.iter().find(|c| c.id == "R1@X1").unwrap().nodes.1).id.clone();
+    let x2_mid = graph.node(graph.components.iter().find(|c| c.id == "R1@X2").unwrap().nodes.1).id.clone();
+    assert_ne!(x1_mid, x2_mid);
+}
+
+/// Subcircuits can reference each other: a body's own `X` instance is
+/// expanded just like a top-level one.
+#[test]
+fn nested_subcircuit_instantiation_is_flattened_recursively() {
+    let text = "\
+.subckt RDIV in out
+R1 in out 1k
+.ends
+.subckt BUF in out
+X1 in out RDIV
+.ends
+Vin a 0 5
+X1 a b BUF
+.end";
+    let graph = parse(text).unwrap();
+    assert!(graph.components.iter().any(|c| c.id == "R1@X1/X1"));
+}
+
+/// A VCVS inside a subcircuit body keeps its control-node pair scoped to
+/// the instance along with everything else.
+#[test]
+fn controlled_source_inside_subckt_scopes_its_controlling_nodes() {
+    let text = "\
+.subckt BUF in out
+E1 out 0 in 0 2
+.ends
+Vin a 0 5
+X1 a b BUF
+.end";
+    let graph = parse(text).unwrap();
+    let e1 = graph.components.iter().find(|c| c.id == "E1@X1").unwrap();
+    match &e1.kind {
+        ComponentKind::VCVS { gain, .. } => assert_eq!(*gain, 2.0),
+        other => panic!("expected VCVS, got {other:?}"),
+    }
+}
+
+/// Instantiating an undeclared subcircuit name is a parse error, not a
+/// panic.
+#[test]
+fn undefined_subcircuit_reference_is_a_parse_error() {
+    let text = "X1 a b GHOST\n.end";
+    assert!(parse(text).is_err());
+}
+
+/// A subcircuit that (directly or transitively) instantiates itself is
+/// rejected instead of recursing forever.
+#[test]
+fn recursive_subcircuit_reference_is_a_parse_error() {
+    let text = "\
+.subckt LOOP a b
+X1 a b LOOP
+.ends
+Vin in 0 5
+X1 in 0 LOOP
+.end";
+    assert!(parse(text).is_err());
+}
+
+/// An `X` line naming fewer or more nodes than the subcircuit declares
+/// ports is a parse error.
+#[test]
+fn port_count_mismatch_is_a_parse_error() {
+    let text = "\
+.subckt RDIV in out
+R1 in out 1k
+.ends
+Vin a 0 5
+X1 a RDIV
+.end";
+    assert!(parse(text).is_err());
+}
+
+/// A `.subckt` block with no matching `.ends` is a parse error.
+#[test]
+fn unterminated_subckt_block_is_a_parse_error() {
+    let text = "\
+.subckt RDIV in out
+R1 in out 1k
+Vin a 0 5
+.end";
+    assert!(parse(text).is_err());
+}
@@ -0,0 +1,125 @@
+use electro_solve::units::*;
+use electro_solve::component::*;
+use electro_solve::graph::*;
+use electro_solve::errors::CircuitError;
+
+/// Two non-ground nodes joined by a voltage source form a single
+/// supernode with exactly one constraint pinning their voltage
+/// difference.
+#[test]
+fn a_voltage_source_between_two_non_ground_nodes_forms_a_supernode() {
+    let mut graph = CircuitGraph::new();
+    let a = graph.add_node("a".to_string());
+    let b = graph.add_node("b".to_string());
+    graph.add_component("V1".to_string(), ComponentKind::VoltageSource { v: Voltage::dc(5.0) }, (a, b));
+
+    let supernodes = graph.supernodes().unwrap();
+
+    assert_eq!(supernodes.len(), 1);
+    let mut nodes = supernodes[0].nodes.clone();
+    nodes.sort();
+    assert_eq!(nodes, vec![a, b]);
+    assert_eq!(supernodes[0].constraints.len(), 1);
+    assert_eq!(supernodes[0].constraints[0].nodes, (a, b));
+    assert_eq!(supernodes[0].constraints[0].voltage, Voltage::dc(5.0));
+}
+
+/// A voltage source with one leg tied to ground doesn't need supernode
+/// treatment -- plain nodal analysis already pins that node's voltage.
+#[test]
+fn a_voltage_source_tied_to_ground_is_not_a_supernode() {
+    let mut graph = CircuitGraph::new();
+    let gnd = graph.add_node("0".to_string());
+    let a = graph.add_node("a".to_string());
+    graph.set_ground(gnd);
+    graph.add_component("V1".to_string(), ComponentKind::VoltageSource { v: Voltage::dc(12.0) }, (gnd, a));
+
+    let supernodes = graph.supernodes().unwrap();
+
+    assert!(supernodes.is_empty());
+}
+
+/// Three voltage sources chaining a-b, b-c, c-d all merge into one
+/// supernode covering every node they touch, with all three constraints
+/// attached.
+#[test]
+fn chained_voltage_sources_merge_into_one_supernode() {
+    let mut graph = CircuitGraph::new();
+    let a = graph.add_node("a".to_string());
+    let b = graph.add_node("b".to_string());
+    let c = graph.add_node("c".to_string());
+    let d = graph.add_node("d".to_string());
+    graph.add_component("V1".to_string(), ComponentKind::VoltageSource { v: Voltage::dc(1.0) }, (a, b));
+    graph.add_component("V2".to_string(), ComponentKind::VoltageSource { v: Voltage::dc(2.0) }, (b, c));
+    graph.add_component("V3".to_string(), ComponentKind::VoltageSource { v: Voltage::dc(3.0) }, (c, d));
+
+    let supernodes = graph.supernodes().unwrap();
+
+    assert_eq!(supernodes.len(), 1);
+    let mut nodes = supernodes[0].nodes.clone();
+    nodes.sort();
+    assert_eq!(nodes, vec![a, b, c, d]);
+    assert_eq!(supernodes[0].constraints.len(), 3);
+}
+
+/// Two voltage sources wired in parallel between the same pair of nodes
+/// over-determine the node voltages and are rejected.
+#[test]
+fn parallel_voltage_sources_are_rejected_as_overdetermined() {
+    let mut graph = CircuitGraph::new();
+    let a = graph.add_node("a".to_string());
+    let b = graph.add_node("b".to_string());
+    graph.add_component("V1".to_string(), ComponentKind::VoltageSource { v: Voltage::dc(5.0) }, (a, b));
+    graph.add_component("V2".to_string(), ComponentKind::VoltageSource { v: Voltage::dc(5.0) }, (a, b));
+
+    let err = graph.supernodes().unwrap_err();
+
+    assert!(matches!(err, CircuitError::OverdeterminedSupernode(..)));
+}
+
+/// Three voltage sources forming a triangle among non-ground nodes are
+/// likewise rejected, since the loop's last edge always reconnects two
+/// nodes already merged by the first two.
+#[test]
+fn a_triangle_of_voltage_sources_is_rejected_as_overdetermined() {
+    let mut graph = CircuitGraph::new();
+    let a = graph.add_node("a".to_string());
+    let b = graph.add_node("b".to_string());
+    let c = graph.add_node("c".to_string());
+    graph.add_component("V1".to_string(), ComponentKind::VoltageSource { v: Voltage::dc(1.0) }, (a, b));
+    graph.add_component("V2".to_string(), ComponentKind::VoltageSource { v: Voltage::dc(2.0) }, (b, c));
+    graph.add_component("V3".to_string(), ComponentKind::VoltageSource { v: Voltage::dc(3.0) }, (c, a));
+
+    let err = graph.supernodes().unwrap_err();
+
+    assert!(matches!(err, CircuitError::OverdeterminedSupernode(..)));
+}
+
+/// A resistor-only circuit has no voltage sources at all, so there are
+/// no supernodes to report.
+#[test]
+fn a_circuit_with_no_voltage_sources_has_no_supernodes() {
+    let mut graph = CircuitGraph::new();
+    let a = graph.add_node("a".to_string());
+    let b = graph.add_node("b".to_string());
+    graph.add_component("R1".to_string(), ComponentKind::Resistor { r: Resistance::known(100.0).unwrap() }, (a, b));
+
+    let supernodes = graph.supernodes().unwrap();
+
+    assert!(supernodes.is_empty());
+}
+
+/// An inactive voltage source (already deactivated, e.g. by a reduction
+/// pass) is ignored entirely.
+#[test]
+fn inactive_voltage_sources_are_ignored() {
+    let mut graph = CircuitGraph::new();
+    let a = graph.add_node("a".to_string());
+    let b = graph.add_node("b".to_string());
+    let v1 = graph.add_component("V1".to_string(), ComponentKind::VoltageSource { v: Voltage::dc(5.0) }, (a, b));
+    graph.components[v1].is_active = false;
+
+    let supernodes = graph.supernodes().unwrap();
+
+    assert!(supernodes.is_empty());
+}
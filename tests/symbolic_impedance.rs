@@ -0,0 +1,138 @@
+use num_complex::Complex64;
+
+use electro_solve::component::ComponentKind;
+use electro_solve::graph::CircuitGraph;
+use electro_solve::symbolic::{graph_sym_impedance, solve_for, sym_combine_parallel, sym_combine_series, SymImpedance};
+use electro_solve::units::*;
+
+mod common;
+use common::*;
+
+fn create_unknown_resistor(graph: &mut CircuitGraph, id: &str, name: &str, n1: usize, n2: usize) -> usize {
+    let kind = ComponentKind::Resistor { r: Resistance::unknown(name.to_string()) };
+    graph.add_component(id.to_string(), kind, (n1, n2))
+}
+
+#[test]
+fn known_resistors_in_series_match_the_numeric_result() {
+    let mut graph = CircuitGraph::new();
+    let values = [100.0, 220.0, 330.0];
+    build_series_chain(&mut graph, &values);
+
+    let omega = AngularFrequency::new(1.0).unwrap();
+    let sym = graph_sym_impedance(&graph, omega).unwrap();
+
+    // No unknowns, so `num / den` should evaluate to a plain constant that
+    // matches the series sum.
+    let num = sym.num.terms.values().next().copied().unwrap_or(Complex64::new(0.0, 0.0));
+    let den = sym.den.terms.values().next().copied().unwrap_or(Complex64::new(1.0, 0.0));
+    let expected = match series_impedance(&values) {
+        ImpedanceResult::Finite(z) => z,
+        other => panic!("expected a finite impedance, got {other:?}"),
+    };
+    assert_complex_eq(num / den, expected, EPSILON_PHYSICAL);
+}
+
+#[test]
+fn a_series_chain_with_one_unknown_is_linear_in_that_symbol() {
+    let mut graph = CircuitGraph::new();
+    graph.add_node("a".to_string());
+    graph.add_node("b".to_string());
+    graph.add_node("c".to_string());
+    graph.set_ground(0);
+
+    create_resistor(&mut graph, "R1", 100.0, 0, 1);
+    create_unknown_resistor(&mut graph, "R2", "Rx", 1, 2);
+
+    let omega = AngularFrequency::new(1.0).unwrap();
+    let sym = graph_sym_impedance(&graph, omega).unwrap();
+
+    assert_eq!(sym.den.degree_in("Rx"), 0);
+    assert_eq!(sym.num.degree_in("Rx"), 1);
+    assert_complex_eq(sym.num.univariate_coeff("Rx", 0), Complex64::new(100.0, 0.0), EPSILON_STRICT);
+    assert_complex_eq(sym.num.univariate_coeff("Rx", 1), Complex64::new(1.0, 0.0), EPSILON_STRICT);
+}
+
+#[test]
+fn solve_for_recovers_the_resistor_that_hits_a_target_series_impedance() {
+    let mut graph = CircuitGraph::new();
+    graph.add_node("a".to_string());
+    graph.add_node("b".to_string());
+    graph.add_node("c".to_string());
+    graph.set_ground(0);
+
+    create_resistor(&mut graph, "R1", 100.0, 0, 1);
+    create_unknown_resistor(&mut graph, "R2", "Rx", 1, 2);
+
+    let omega = AngularFrequency::new(1.0).unwrap();
+    let target = ImpedanceResult::new_finite(Complex64::new(350.0, 0.0));
+    let roots = solve_for(&graph, "Rx", target, omega).unwrap();
+
+    assert_eq!(roots.len(), 1);
+    assert_relative_eq(roots[0], 250.0);
+}
+
+#[test]
+fn solve_for_a_parallel_bank_clears_the_denominator_correctly() {
+    // Rx appears in both the numerator and denominator of the parallel
+    // combination, so solving this exercises the "clear denominators
+    // before solving" step rather than a bare linear equation.
+    let mut graph = CircuitGraph::new();
+    graph.add_node("a".to_string());
+    graph.add_node("b".to_string());
+    graph.set_ground(0);
+
+    create_resistor(&mut graph, "R1", 200.0, 0, 1);
+    create_unknown_resistor(&mut graph, "R2", "Rx", 0, 1);
+
+    let omega = AngularFrequency::new(1.0).unwrap();
+    // 200 || Rx == 120  =>  Rx == 300
+    let target = ImpedanceResult::new_finite(Complex64::new(120.0, 0.0));
+    let roots = solve_for(&graph, "Rx", target, omega).unwrap();
+
+    assert!(roots.iter().any(|&r| (r - 300.0).abs() < EPSILON_PHYSICAL));
+}
+
+#[test]
+fn sym_combine_series_and_parallel_match_their_numeric_counterparts() {
+    let a = SymImpedance::known(Complex64::new(100.0, 0.0));
+    let b = SymImpedance::known(Complex64::new(200.0, 0.0));
+
+    let series = sym_combine_series(&a, &b);
+    let parallel = sym_combine_parallel(&a, &b);
+
+    let eval = |z: &SymImpedance| {
+        let num = z.num.terms.values().next().copied().unwrap_or(Complex64::new(0.0, 0.0));
+        let den = z.den.terms.values().next().copied().unwrap_or(Complex64::new(1.0, 0.0));
+        num / den
+    };
+
+    assert_complex_eq(eval(&series), Complex64::new(300.0, 0.0), EPSILON_PHYSICAL);
+    assert_complex_eq(eval(&parallel), Complex64::new(400.0 / 3.0, 0.0), EPSILON_PHYSICAL);
+}
+
+#[test]
+fn a_bridge_topology_is_rejected_as_unsupported() {
+    let mut graph = CircuitGraph::new();
+    let a = graph.add_node("a".to_string());
+    let b = graph.add_node("b".to_string());
+    let top = graph.add_node("top".to_string());
+    let bottom = graph.add_node("bottom".to_string());
+    graph.set_ground(b);
+
+    create_resistor(&mut graph, "R1", 100.0, a, top);
+    create_resistor(&mut graph, "R2", 200.0, a, bottom);
+    create_resistor(&mut graph, "R3", 100.0, top, b);
+    create_resistor(&mut graph, "R4", 200.0, bottom, b);
+    create_unknown_resistor(&mut graph, "Rg", "Rx", top, bottom);
+
+    let omega = AngularFrequency::new(1.0).unwrap();
+    assert!(graph_sym_impedance(&graph, omega).is_err());
+}
+
+fn assert_relative_eq(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < EPSILON_PHYSICAL,
+        "expected {expected}, got {actual}"
+    );
+}
@@ -0,0 +1,65 @@
+use electro_solve::graph::CircuitGraph;
+use electro_solve::transient::response;
+
+mod common;
+use common::create_resistor;
+
+#[test]
+fn a_pure_resistor_passes_the_waveform_through_scaled_by_r() {
+    let mut graph = CircuitGraph::new();
+    graph.add_node("a".to_string());
+    graph.add_node("b".to_string());
+    graph.set_ground(1);
+    create_resistor(&mut graph, "R1", 4.0, 0, 1);
+
+    // A resistor's impedance is frequency-independent, so the network
+    // just scales every sample by R -- no phase shift, no smearing.
+    let waveform: Vec<f64> = (0..8).map(|n| (n as f64 * 0.7).sin()).collect();
+    let out = response(&graph, 0, 1, &waveform, 1000.0).unwrap();
+
+    assert_eq!(out.len(), 8);
+    for (x, y) in waveform.iter().zip(out.iter()) {
+        assert!((y - 4.0 * x).abs() < 1e-9, "expected {} got {}", 4.0 * x, y);
+    }
+}
+
+#[test]
+fn waveform_shorter_than_a_power_of_two_is_zero_padded() {
+    let mut graph = CircuitGraph::new();
+    graph.add_node("a".to_string());
+    graph.add_node("b".to_string());
+    graph.set_ground(1);
+    create_resistor(&mut graph, "R1", 2.0, 0, 1);
+
+    // 5 samples zero-pads to 8; the response should still be defined
+    // (and still scaled by R) for every input sample.
+    let waveform = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+    let out = response(&graph, 0, 1, &waveform, 1000.0).unwrap();
+
+    assert_eq!(out.len(), 8);
+    for (x, y) in waveform.iter().zip(out.iter()) {
+        assert!((y - 2.0 * x).abs() < 1e-9, "expected {} got {}", 2.0 * x, y);
+    }
+}
+
+#[test]
+fn zero_frequency_rejects_an_invalid_sample_rate() {
+    let mut graph = CircuitGraph::new();
+    graph.add_node("a".to_string());
+    graph.add_node("b".to_string());
+    graph.set_ground(1);
+    create_resistor(&mut graph, "R1", 2.0, 0, 1);
+
+    assert!(response(&graph, 0, 1, &[1.0, 2.0, 3.0, 4.0], 0.0).is_err());
+}
+
+#[test]
+fn an_empty_waveform_is_rejected() {
+    let mut graph = CircuitGraph::new();
+    graph.add_node("a".to_string());
+    graph.add_node("b".to_string());
+    graph.set_ground(1);
+    create_resistor(&mut graph, "R1", 2.0, 0, 1);
+
+    assert!(response(&graph, 0, 1, &[], 1000.0).is_err());
+}